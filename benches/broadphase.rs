@@ -0,0 +1,97 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use glam::Vec2;
+use hecs::{Entity, World};
+use legends_of_legend::engine::core::{Rect, Transform};
+use legends_of_legend::engine::physics::{Collider, CollisionLayers, SpatialHash};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+const ENEMY_COUNT: usize = 400;
+const PELLET_COUNT: usize = 4000;
+const ARENA_SIZE: Vec2 = Vec2::new(8000.0, 1200.0);
+
+/// A level's worth of enemies and a screen full of shotgun pellets
+fn populate() -> (World, Vec<(Entity, Vec2)>) {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut world = World::new();
+
+    for _ in 0..ENEMY_COUNT {
+        let position = Vec2::new(rng.gen_range(0.0..ARENA_SIZE.x), rng.gen_range(0.0..ARENA_SIZE.y));
+        world.spawn((Transform::new(position), Collider::Box { size: Vec2::new(32.0, 48.0) }));
+    }
+
+    let mut pellets = Vec::new();
+    for _ in 0..PELLET_COUNT {
+        let position = Vec2::new(rng.gen_range(0.0..ARENA_SIZE.x), rng.gen_range(0.0..ARENA_SIZE.y));
+        let entity = world.spawn((Transform::new(position), Collider::Circle { radius: 3.0 }));
+        pellets.push((entity, position));
+    }
+
+    (world, pellets)
+}
+
+fn pellet_rect(position: Vec2) -> Rect {
+    Rect::new(position.x - 3.0, position.y - 3.0, 6.0, 6.0)
+}
+
+fn bench_broadphase(c: &mut Criterion) {
+    let (world, pellets) = populate();
+    let enemies: Vec<(Vec2, Vec2)> = world
+        .query::<(&Transform, &Collider)>()
+        .iter()
+        .filter_map(|(_, (transform, collider))| match collider {
+            Collider::Box { size } => Some((transform.position, *size)),
+            Collider::Circle { .. } => None,
+        })
+        .collect();
+
+    let mut broadphase = SpatialHash::default();
+    broadphase.rebuild(&world);
+
+    c.bench_function("rebuild", |b| {
+        b.iter(|| broadphase.rebuild(black_box(&world)))
+    });
+
+    // The old projectile_system approach: every pellet against every enemy
+    c.bench_function("pellet_hits_nested_loop", |b| {
+        b.iter(|| {
+            let mut hits = 0;
+            for (_, position) in &pellets {
+                let rect = pellet_rect(*position);
+                for (enemy_position, size) in &enemies {
+                    let enemy_rect = Rect::new(
+                        enemy_position.x - size.x / 2.0,
+                        enemy_position.y - size.y / 2.0,
+                        size.x,
+                        size.y,
+                    );
+                    if enemy_rect.intersects(&rect) {
+                        hits += 1;
+                    }
+                }
+            }
+            black_box(hits)
+        })
+    });
+
+    c.bench_function("pellet_hits_spatial_hash", |b| {
+        b.iter(|| {
+            let mut hits = 0;
+            for (_, position) in &pellets {
+                hits += broadphase.query_rect(pellet_rect(*position), CollisionLayers::ALL).len();
+            }
+            black_box(hits)
+        })
+    });
+
+    c.bench_function("raycast", |b| {
+        b.iter(|| {
+            black_box(broadphase.raycast(Vec2::new(0.0, 600.0), Vec2::new(1.0, 0.05), ARENA_SIZE.x, CollisionLayers::ALL).len())
+        })
+    });
+
+    c.bench_function("pairs", |b| b.iter(|| black_box(broadphase.pairs().len())));
+}
+
+criterion_group!(benches, bench_broadphase);
+criterion_main!(benches);
@@ -77,6 +77,17 @@ impl Color {
         Self { r, g, b, a }
     }
 
+    /// Linearly blend towards another color (t = 0.0 returns self, t = 1.0 returns other)
+    pub fn lerp(&self, other: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        Color::new(
+            (self.r as f32 + (other.r as f32 - self.r as f32) * t) as u8,
+            (self.g as f32 + (other.g as f32 - self.g as f32) * t) as u8,
+            (self.b as f32 + (other.b as f32 - self.b as f32) * t) as u8,
+            (self.a as f32 + (other.a as f32 - self.a as f32) * t) as u8,
+        )
+    }
+
     pub fn to_sdl(&self) -> sdl2::pixels::Color {
         sdl2::pixels::Color::RGBA(self.r, self.g, self.b, self.a)
     }
@@ -40,6 +40,9 @@ impl Engine {
             last_time = current_time;
 
             self.platform.handle_events(&mut self.running)?;
+            if let Some((width, height)) = self.platform.resized {
+                self.renderer.resize(width, height);
+            }
 
             update(self, self.delta_time);
 
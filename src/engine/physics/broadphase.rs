@@ -0,0 +1,272 @@
+use crate::engine::core::{Rect, Transform};
+use crate::engine::physics::{Collider, CollisionFilter, CollisionLayers};
+use glam::Vec2;
+use hecs::{Entity, World};
+use std::collections::HashMap;
+
+/// Default bucket size - a bit larger than a typical enemy
+pub const BROADPHASE_CELL_SIZE: f32 = 64.0;
+
+type CellKey = (i32, i32);
+
+/// An indexed collider and where it was at the last rebuild
+#[derive(Debug, Clone)]
+pub struct BroadphaseEntry {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub collider: Collider,
+    pub filter: CollisionFilter,
+    pub bounds: Rect,
+}
+
+/// A hit returned by `SpatialHash::raycast`
+#[derive(Debug, Clone, Copy)]
+pub struct RayHit {
+    pub entity: Entity,
+    pub distance: f32,
+    pub point: Vec2,
+}
+
+/// Uniform grid that buckets collider bounds so proximity queries only look at nearby entities.
+/// Rebuilt from every `Transform` + `Collider` entity each tick. Queries take a layer mask
+/// (or a full `CollisionFilter`) so callers only see the kinds of entity they care about.
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: HashMap<CellKey, Vec<usize>>,
+    entries: Vec<BroadphaseEntry>,
+    lookup: HashMap<Entity, usize>,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            entries: Vec::new(),
+            lookup: HashMap::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        // Keep the bucket allocations around for the next rebuild
+        for bucket in self.cells.values_mut() {
+            bucket.clear();
+        }
+        self.entries.clear();
+        self.lookup.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Re-index every entity that has a `Transform` and a `Collider`
+    pub fn rebuild(&mut self, world: &World) {
+        self.clear();
+        for (entity, (transform, collider, filter)) in world
+            .query::<(&Transform, &Collider, Option<&CollisionFilter>)>()
+            .iter()
+        {
+            self.insert(entity, transform.position, collider.clone(), filter.copied().unwrap_or_default());
+        }
+    }
+
+    pub fn insert(&mut self, entity: Entity, position: Vec2, collider: Collider, filter: CollisionFilter) {
+        let bounds = collider.bounds(position);
+        let index = self.entries.len();
+        let (min, max) = self.cell_range(&bounds);
+        for cy in min.1..=max.1 {
+            for cx in min.0..=max.0 {
+                self.cells.entry((cx, cy)).or_default().push(index);
+            }
+        }
+        self.lookup.insert(entity, index);
+        self.entries.push(BroadphaseEntry {
+            entity,
+            position,
+            collider,
+            filter,
+            bounds,
+        });
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&BroadphaseEntry> {
+        self.lookup.get(&entity).map(|&index| &self.entries[index])
+    }
+
+    /// Entities on any of the `layers` whose bounds overlap a rect
+    pub fn query_rect(&self, rect: Rect, layers: CollisionLayers) -> Vec<Entity> {
+        self.candidates(&rect)
+            .into_iter()
+            .map(|index| &self.entries[index])
+            .filter(|entry| entry.filter.layer.intersects(layers) && entry.bounds.intersects(&rect))
+            .map(|entry| entry.entity)
+            .collect()
+    }
+
+    /// Entities a collider with `filter` would interact with if its bounds were `rect`
+    pub fn query_filtered(&self, rect: Rect, filter: &CollisionFilter) -> Vec<Entity> {
+        self.candidates(&rect)
+            .into_iter()
+            .map(|index| &self.entries[index])
+            .filter(|entry| filter.interacts_with(&entry.filter) && entry.bounds.intersects(&rect))
+            .map(|entry| entry.entity)
+            .collect()
+    }
+
+    /// Entities on any of the `layers` whose collider actually touches a collider placed at `position`
+    pub fn query_collider(&self, position: Vec2, collider: &Collider, layers: CollisionLayers) -> Vec<Entity> {
+        self.candidates(&collider.bounds(position))
+            .into_iter()
+            .map(|index| &self.entries[index])
+            .filter(|entry| entry.filter.layer.intersects(layers))
+            .filter(|entry| collider.check_collision(position, &entry.collider, entry.position))
+            .map(|entry| entry.entity)
+            .collect()
+    }
+
+    /// Entities on any of the `layers` touching a circle
+    pub fn query_circle(&self, center: Vec2, radius: f32, layers: CollisionLayers) -> Vec<Entity> {
+        self.query_collider(center, &Collider::Circle { radius }, layers)
+    }
+
+    /// Entities on any of the `layers` whose bounds a ray passes through, nearest first
+    pub fn raycast(&self, origin: Vec2, direction: Vec2, max_distance: f32, layers: CollisionLayers) -> Vec<RayHit> {
+        let direction = direction.normalize_or_zero();
+        if direction == Vec2::ZERO {
+            return Vec::new();
+        }
+
+        // Walk the cells along the ray (Amanatides-Woo), testing each bucket once
+        let mut hits: Vec<RayHit> = Vec::new();
+        let mut seen = vec![false; self.entries.len()];
+        let mut cell = self.cell_of(origin);
+        let step = (
+            if direction.x > 0.0 { 1 } else { -1 },
+            if direction.y > 0.0 { 1 } else { -1 },
+        );
+        let boundary = |c: i32, s: i32| (c + if s > 0 { 1 } else { 0 }) as f32 * self.cell_size;
+        let mut t_max = Vec2::new(
+            if direction.x != 0.0 { (boundary(cell.0, step.0) - origin.x) / direction.x } else { f32::INFINITY },
+            if direction.y != 0.0 { (boundary(cell.1, step.1) - origin.y) / direction.y } else { f32::INFINITY },
+        );
+        let t_delta = Vec2::new(
+            if direction.x != 0.0 { self.cell_size / direction.x.abs() } else { f32::INFINITY },
+            if direction.y != 0.0 { self.cell_size / direction.y.abs() } else { f32::INFINITY },
+        );
+
+        let mut travelled = 0.0;
+        while travelled <= max_distance {
+            if let Some(bucket) = self.cells.get(&cell) {
+                for &index in bucket {
+                    if seen[index] {
+                        continue;
+                    }
+                    seen[index] = true;
+                    let entry = &self.entries[index];
+                    if !entry.filter.layer.intersects(layers) {
+                        continue;
+                    }
+                    if let Some(distance) = ray_rect_distance(origin, direction, &entry.bounds) {
+                        if distance <= max_distance {
+                            hits.push(RayHit {
+                                entity: entry.entity,
+                                distance,
+                                point: origin + direction * distance,
+                            });
+                        }
+                    }
+                }
+            }
+
+            if t_max.x < t_max.y {
+                travelled = t_max.x;
+                t_max.x += t_delta.x;
+                cell.0 += step.0;
+            } else {
+                travelled = t_max.y;
+                t_max.y += t_delta.y;
+                cell.1 += step.1;
+            }
+        }
+
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// Every pair of entities whose colliders touch and whose filters let them interact,
+    /// each pair reported once
+    pub fn pairs(&self) -> Vec<(Entity, Entity)> {
+        let mut pairs = Vec::new();
+        for (a, entry) in self.entries.iter().enumerate() {
+            for b in self.candidates(&entry.bounds) {
+                // Only report from the lower index so each pair shows up once
+                if b <= a {
+                    continue;
+                }
+                let other = &self.entries[b];
+                if entry.filter.interacts_with(&other.filter)
+                    && entry.collider.check_collision(entry.position, &other.collider, other.position)
+                {
+                    pairs.push((entry.entity, other.entity));
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Indices of every entry sharing a cell with a rect, without duplicates
+    fn candidates(&self, rect: &Rect) -> Vec<usize> {
+        let (min, max) = self.cell_range(rect);
+        let mut found = Vec::new();
+        for cy in min.1..=max.1 {
+            for cx in min.0..=max.0 {
+                if let Some(bucket) = self.cells.get(&(cx, cy)) {
+                    found.extend_from_slice(bucket);
+                }
+            }
+        }
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+
+    fn cell_of(&self, point: Vec2) -> CellKey {
+        (
+            (point.x / self.cell_size).floor() as i32,
+            (point.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    fn cell_range(&self, rect: &Rect) -> (CellKey, CellKey) {
+        (
+            self.cell_of(Vec2::new(rect.x, rect.y)),
+            self.cell_of(Vec2::new(rect.x + rect.width, rect.y + rect.height)),
+        )
+    }
+}
+
+impl Default for SpatialHash {
+    fn default() -> Self {
+        Self::new(BROADPHASE_CELL_SIZE)
+    }
+}
+
+/// Distance along a normalised ray to where it enters a rect (0 if it starts inside)
+fn ray_rect_distance(origin: Vec2, direction: Vec2, rect: &Rect) -> Option<f32> {
+    let inverse = Vec2::ONE / direction;
+    let t1 = (Vec2::new(rect.x, rect.y) - origin) * inverse;
+    let t2 = (Vec2::new(rect.x + rect.width, rect.y + rect.height) - origin) * inverse;
+    let t_enter = t1.min(t2).max_element();
+    let t_exit = t1.max(t2).min_element();
+
+    if t_exit < 0.0 || t_enter > t_exit {
+        None
+    } else {
+        Some(t_enter.max(0.0))
+    }
+}
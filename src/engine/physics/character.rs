@@ -0,0 +1,391 @@
+use crate::engine::core::{Rect, Transform};
+use crate::engine::physics::RigidBody;
+use crate::game::{Level, TILE_SIZE};
+use glam::Vec2;
+
+/// Longest distance moved in one sweep step - keeps fast bodies from tunnelling through tiles
+const MAX_SWEEP_STEP: f32 = TILE_SIZE / 4.0;
+/// Gap left between a body and the tile it was pushed out of
+const CONTACT_SKIN: f32 = 0.01;
+/// Seconds one-way platforms are ignored after dropping through one
+const DROP_THROUGH_TIME: f32 = 0.2;
+/// Height a grounded body can step up per pixel moved sideways - enough for 45 degree ramps
+const MAX_CLIMB_SLOPE: f32 = 1.0;
+/// Extra reach when pulling a grounded body down onto a ramp it is walking down
+const GROUND_SNAP_DISTANCE: f32 = 2.0;
+/// Bisection passes used to close the gap to whatever a sweep ran into
+const CONTACT_REFINE_STEPS: u32 = 8;
+
+/// Which sides of a character touched the level during the last physics step
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Contacts {
+    pub on_ground: bool,
+    pub on_ceiling: bool,
+    pub on_wall_left: bool,
+    pub on_wall_right: bool,
+    /// Standing on a one-way platform rather than solid ground
+    pub on_platform: bool,
+    /// Only true for the step in which the body touched down
+    pub just_landed: bool,
+    /// Downward speed the body hit the ground with
+    pub landing_speed: f32,
+}
+
+/// Kinematic body for anything that walks on the tile grid.
+/// Controllers set the RigidBody velocity they want; `character_physics_system`
+/// adds gravity, resolves tile collisions and reports the resulting contacts.
+#[derive(Debug, Clone)]
+pub struct CharacterBody {
+    pub size: Vec2,
+    pub gravity: f32,
+    pub gravity_scale: f32,
+    pub terminal_velocity: f32,
+    /// Disabled bodies are left alone (e.g. a pilot sealed inside a mech)
+    pub enabled: bool,
+    /// Time left ignoring one-way platforms
+    pub drop_timer: f32,
+    pub contacts: Contacts,
+}
+
+impl CharacterBody {
+    pub fn new(size: Vec2, gravity: f32) -> Self {
+        Self {
+            size,
+            gravity,
+            gravity_scale: 1.0,
+            terminal_velocity: 1200.0,
+            enabled: true,
+            drop_timer: 0.0,
+            contacts: Contacts::default(),
+        }
+    }
+
+    pub fn with_terminal_velocity(mut self, terminal_velocity: f32) -> Self {
+        self.terminal_velocity = terminal_velocity;
+        self
+    }
+
+    pub fn is_grounded(&self) -> bool {
+        self.contacts.on_ground
+    }
+
+    /// Fall through the one-way platform underfoot. Returns false when not standing on one.
+    pub fn drop_through(&mut self) -> bool {
+        if !self.contacts.on_platform {
+            return false;
+        }
+        self.drop_timer = DROP_THROUGH_TIME;
+        self.contacts.on_ground = false;
+        self.contacts.on_platform = false;
+        true
+    }
+
+    fn is_dropping(&self) -> bool {
+        self.drop_timer > 0.0
+    }
+
+    /// Collision rect for the body centred at `position`
+    pub fn rect_at(&self, position: Vec2) -> Rect {
+        Rect::new(
+            position.x - self.size.x / 2.0,
+            position.y - self.size.y / 2.0,
+            self.size.x,
+            self.size.y,
+        )
+    }
+
+    /// Thin strip under the whole width of the feet, used for the grounded check
+    fn foot_probe(&self, position: Vec2) -> Rect {
+        Rect::new(
+            position.x - self.size.x / 2.0,
+            position.y + self.size.y / 2.0,
+            self.size.x,
+            2.0,
+        )
+    }
+}
+
+/// Move every enabled character by its velocity against the level
+pub fn character_physics_system(world: &mut hecs::World, level: &Level, delta_time: f32) {
+    for (_entity, (character, transform, body)) in
+        world.query_mut::<(&mut CharacterBody, &mut Transform, &mut RigidBody)>()
+    {
+        if !character.enabled {
+            continue;
+        }
+        step_character(character, &mut transform.position, &mut body.velocity, level, delta_time);
+    }
+}
+
+/// One physics step: gravity, terminal velocity, axis-separated sweeps, then contact detection
+pub fn step_character(
+    character: &mut CharacterBody,
+    position: &mut Vec2,
+    velocity: &mut Vec2,
+    level: &Level,
+    delta_time: f32,
+) {
+    let was_grounded = character.contacts.on_ground;
+    let mut contacts = Contacts::default();
+    character.drop_timer = (character.drop_timer - delta_time).max(0.0);
+
+    velocity.y += character.gravity * character.gravity_scale * delta_time;
+    velocity.y = velocity.y.min(character.terminal_velocity);
+    let fall_speed = velocity.y;
+
+    // Horizontal first so walking into a wall doesn't stop a fall. Grounded bodies walk up ramps.
+    let dx = velocity.x * delta_time;
+    if sweep_axis(character, position, Vec2::new(dx, 0.0), level, was_grounded) {
+        if dx > 0.0 {
+            contacts.on_wall_right = true;
+        } else {
+            contacts.on_wall_left = true;
+        }
+        velocity.x = 0.0;
+    }
+
+    let dy = velocity.y * delta_time;
+    if sweep_axis(character, position, Vec2::new(0.0, dy), level, false) {
+        if dy > 0.0 {
+            contacts.on_ground = true;
+        } else {
+            // Bumped our head
+            contacts.on_ceiling = true;
+        }
+        velocity.y = 0.0;
+    }
+
+    // Stick to the ground when walking down ramps instead of skipping off them
+    if was_grounded && !contacts.on_ground && velocity.y >= 0.0 {
+        let mut snapped = *position;
+        let reach = dx.abs() * MAX_CLIMB_SLOPE + GROUND_SNAP_DISTANCE;
+        if sweep_axis(character, &mut snapped, Vec2::new(0.0, reach), level, false) {
+            *position = snapped;
+            contacts.on_ground = true;
+            velocity.y = 0.0;
+        }
+    }
+
+    // Keep inside the level horizontally
+    let half_width = character.size.x / 2.0;
+    position.x = position.x.clamp(half_width, level.width as f32 * TILE_SIZE - half_width);
+
+    // Solid ground under the feet wins over a one-way platform at the same height
+    if velocity.y >= 0.0 {
+        let bottom = position.y + character.size.y / 2.0;
+        let on_solid = level.check_collision(character.foot_probe(*position));
+        contacts.on_platform = !on_solid
+            && !character.is_dropping()
+            && level
+                .one_way_landing(character.rect_at(*position), bottom, bottom + 2.0)
+                .is_some();
+        contacts.on_ground |= on_solid || contacts.on_platform;
+    }
+
+    if contacts.on_ground && !was_grounded {
+        contacts.just_landed = true;
+        contacts.landing_speed = fall_speed.max(0.0);
+    }
+
+    character.contacts = contacts;
+}
+
+/// Shove a character sideways (e.g. out of another body) without pushing it into the level
+pub fn push_character(character: &CharacterBody, position: &mut Vec2, offset: f32, level: &Level) {
+    sweep_axis(character, position, Vec2::new(offset, 0.0), level, character.contacts.on_ground);
+}
+
+/// Move along one axis in small steps, stopping flush against the first tile hit.
+/// With `climb` set, sideways moves step up ramps and small lips instead of stopping.
+/// Returns true if the move was blocked.
+fn sweep_axis(character: &CharacterBody, position: &mut Vec2, delta: Vec2, level: &Level, climb: bool) -> bool {
+    let distance = delta.length();
+    if distance == 0.0 {
+        return false;
+    }
+
+    let steps = (distance / MAX_SWEEP_STEP).ceil().max(1.0) as usize;
+    let step = delta / steps as f32;
+    let half = character.size / 2.0;
+
+    for _ in 0..steps {
+        let next = *position + step;
+
+        // One-way platforms only stop bodies falling onto them from above
+        if step.y > 0.0 && !character.is_dropping() {
+            let rect = character.rect_at(next);
+            if let Some(top) = level.one_way_landing(rect, position.y + half.y, next.y + half.y) {
+                position.y = top - half.y - CONTACT_SKIN;
+                return true;
+            }
+        }
+
+        if !level.check_collision(character.rect_at(next)) {
+            *position = next;
+            continue;
+        }
+
+        if climb && step.x != 0.0 {
+            if let Some(lifted) = climb_step(character, next, step.x.abs() * MAX_CLIMB_SLOPE, level) {
+                *position = lifted;
+                continue;
+            }
+        }
+
+        // Close the gap to whatever we hit - works for ramps as well as full tiles
+        let (mut free, mut blocked) = (0.0, 1.0);
+        for _ in 0..CONTACT_REFINE_STEPS {
+            let mid = (free + blocked) / 2.0;
+            if level.check_collision(character.rect_at(*position + step * mid)) {
+                blocked = mid;
+            } else {
+                free = mid;
+            }
+        }
+        *position += step * free;
+        return true;
+    }
+
+    false
+}
+
+/// Lowest clear position at most `max_lift` above `position`, for stepping up ramps
+fn climb_step(character: &CharacterBody, position: Vec2, max_lift: f32, level: &Level) -> Option<Vec2> {
+    let mut lift = 1.0;
+    while lift <= max_lift + 1.0 {
+        let lifted = position - Vec2::new(0.0, lift);
+        if !level.check_collision(character.rect_at(lifted)) {
+            return Some(lifted);
+        }
+        lift += 1.0;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP: f32 = 1.0 / 60.0;
+    const GRAVITY: f32 = 800.0;
+
+    /// Ten tiles wide: a platform over columns 3..7 with its top at y = 128,
+    /// and ground whose top is at y = 224
+    fn platform_level() -> Level {
+        Level::from_string(
+            "..........\n\
+             ..........\n\
+             ..........\n\
+             ..........\n\
+             ...====...\n\
+             ..........\n\
+             ..........\n\
+             ##########\n\
+             ##########\n\
+             ##########",
+        )
+    }
+
+    const PLATFORM_TOP: f32 = 4.0 * TILE_SIZE;
+    const GROUND_TOP: f32 = 7.0 * TILE_SIZE;
+
+    fn body() -> CharacterBody {
+        CharacterBody::new(Vec2::new(24.0, 48.0), GRAVITY)
+    }
+
+    fn bottom(character: &CharacterBody, position: Vec2) -> f32 {
+        position.y + character.size.y / 2.0
+    }
+
+    /// Run a second of steps, long enough to land and come to rest.
+    /// Returns whether any step reported touching down.
+    fn settle(character: &mut CharacterBody, position: &mut Vec2, velocity: &mut Vec2, level: &Level) -> bool {
+        let mut landed = false;
+        for _ in 0..60 {
+            step_character(character, position, velocity, level, STEP);
+            landed |= character.contacts.just_landed;
+        }
+        landed
+    }
+
+    #[test]
+    fn lands_on_platform_at_terminal_velocity() {
+        let level = platform_level();
+        // Fast enough to cover most of a tile per step
+        let mut character = body().with_terminal_velocity(1800.0);
+        let mut position = Vec2::new(160.0, 24.0);
+        let mut velocity = Vec2::new(0.0, 1800.0);
+
+        assert!(settle(&mut character, &mut position, &mut velocity, &level));
+
+        assert!(character.contacts.on_platform);
+        assert!((bottom(&character, position) - PLATFORM_TOP).abs() < 0.1);
+        assert_eq!(velocity.y, 0.0);
+    }
+
+    #[test]
+    fn lands_on_platform_edge_with_small_overlap() {
+        let level = platform_level();
+        let platform_left = 3.0 * TILE_SIZE;
+
+        // Three pixels of the body hang over the platform's left edge
+        let mut character = body();
+        let mut position = Vec2::new(platform_left - character.size.x / 2.0 + 3.0, 24.0);
+        let mut velocity = Vec2::ZERO;
+        settle(&mut character, &mut position, &mut velocity, &level);
+        assert!(character.contacts.on_platform);
+        assert!((bottom(&character, position) - PLATFORM_TOP).abs() < 0.1);
+
+        // One pixel clear of the edge misses it and reaches the ground
+        let mut character = body();
+        let mut position = Vec2::new(platform_left - character.size.x / 2.0 - 1.0, 24.0);
+        let mut velocity = Vec2::ZERO;
+        settle(&mut character, &mut position, &mut velocity, &level);
+        assert!(!character.contacts.on_platform);
+        assert!((bottom(&character, position) - GROUND_TOP).abs() < 0.1);
+    }
+
+    #[test]
+    fn jumps_up_through_platform_and_lands_on_it() {
+        let level = platform_level();
+        let mut character = body();
+        let mut position = Vec2::new(160.0, GROUND_TOP - character.size.y / 2.0 - CONTACT_SKIN);
+        let mut velocity = Vec2::ZERO;
+        step_character(&mut character, &mut position, &mut velocity, &level, STEP);
+        assert!(character.is_grounded());
+
+        // High enough to clear the platform with room to spare
+        velocity.y = -550.0;
+        let mut rose_above = false;
+        for _ in 0..120 {
+            step_character(&mut character, &mut position, &mut velocity, &level, STEP);
+            assert!(!character.contacts.on_ceiling, "platform blocked the jump from below");
+            rose_above |= bottom(&character, position) < PLATFORM_TOP;
+        }
+
+        assert!(rose_above);
+        assert!(character.contacts.on_platform);
+        assert!((bottom(&character, position) - PLATFORM_TOP).abs() < 0.1);
+    }
+
+    #[test]
+    fn drop_through_leaves_platform_for_ground() {
+        let level = platform_level();
+        let mut character = body();
+        let mut position = Vec2::new(160.0, PLATFORM_TOP - character.size.y / 2.0 - CONTACT_SKIN);
+        let mut velocity = Vec2::ZERO;
+        step_character(&mut character, &mut position, &mut velocity, &level, STEP);
+        assert!(character.contacts.on_platform);
+
+        // What the controllers do on Down + Jump
+        assert!(character.drop_through());
+        assert!(!character.is_grounded());
+        settle(&mut character, &mut position, &mut velocity, &level);
+
+        assert!(!character.contacts.on_platform);
+        assert!((bottom(&character, position) - GROUND_TOP).abs() < 0.1);
+
+        // Solid ground can't be dropped through
+        assert!(!character.drop_through());
+    }
+}
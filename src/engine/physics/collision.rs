@@ -8,6 +8,15 @@ pub enum Collider {
 }
 
 impl Collider {
+    /// Axis-aligned box enclosing the collider at `position`
+    pub fn bounds(&self, position: Vec2) -> Rect {
+        let half = match self {
+            Collider::Box { size } => *size / 2.0,
+            Collider::Circle { radius } => Vec2::splat(*radius),
+        };
+        Rect::new(position.x - half.x, position.y - half.y, half.x * 2.0, half.y * 2.0)
+    }
+
     pub fn check_collision(&self, pos1: Vec2, other: &Collider, pos2: Vec2) -> bool {
         match (self, other) {
             (Collider::Box { size: size1 }, Collider::Box { size: size2 }) => {
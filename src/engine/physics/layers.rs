@@ -0,0 +1,146 @@
+use std::ops::{BitOr, BitOrAssign};
+
+/// Bitset of collision layers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CollisionLayers(u32);
+
+impl CollisionLayers {
+    pub const NONE: Self = Self(0);
+    pub const PLAYER: Self = Self(1 << 0);
+    pub const ENEMY: Self = Self(1 << 1);
+    pub const MECH: Self = Self(1 << 2);
+    /// Shots fired by the player and their mechs
+    pub const FRIENDLY_PROJECTILE: Self = Self(1 << 3);
+    /// Shots fired by enemies
+    pub const HOSTILE_PROJECTILE: Self = Self(1 << 4);
+    pub const PICKUP: Self = Self(1 << 5);
+    pub const TRIGGER: Self = Self(1 << 6);
+    pub const DEBRIS: Self = Self(1 << 7);
+    pub const ALL: Self = Self(u32::MAX);
+
+    pub const fn bits(&self) -> u32 {
+        self.0
+    }
+
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// True when the two sets share at least one layer
+    pub const fn intersects(&self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl Default for CollisionLayers {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl BitOr for CollisionLayers {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+impl BitOrAssign for CollisionLayers {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+/// Which layer a collider sits on and which layers it reacts to.
+/// Two colliders only interact when each one's mask includes the other's layer.
+/// Colliders without a filter interact with everything.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollisionFilter {
+    pub layer: CollisionLayers,
+    pub mask: CollisionLayers,
+}
+
+impl CollisionFilter {
+    pub const fn new(layer: CollisionLayers, mask: CollisionLayers) -> Self {
+        Self { layer, mask }
+    }
+
+    pub const fn player() -> Self {
+        Self::new(
+            CollisionLayers::PLAYER,
+            CollisionLayers::ENEMY
+                .union(CollisionLayers::MECH)
+                .union(CollisionLayers::HOSTILE_PROJECTILE)
+                .union(CollisionLayers::PICKUP)
+                .union(CollisionLayers::TRIGGER),
+        )
+    }
+
+    pub const fn enemy() -> Self {
+        Self::new(
+            CollisionLayers::ENEMY,
+            CollisionLayers::PLAYER
+                .union(CollisionLayers::ENEMY)
+                .union(CollisionLayers::MECH)
+                .union(CollisionLayers::FRIENDLY_PROJECTILE)
+                .union(CollisionLayers::TRIGGER),
+        )
+    }
+
+    pub const fn mech() -> Self {
+        Self::new(
+            CollisionLayers::MECH,
+            CollisionLayers::PLAYER
+                .union(CollisionLayers::ENEMY)
+                .union(CollisionLayers::MECH)
+                .union(CollisionLayers::HOSTILE_PROJECTILE)
+                .union(CollisionLayers::PICKUP)
+                .union(CollisionLayers::TRIGGER),
+        )
+    }
+
+    pub const fn friendly_projectile() -> Self {
+        Self::new(CollisionLayers::FRIENDLY_PROJECTILE, CollisionLayers::ENEMY)
+    }
+
+    pub const fn hostile_projectile() -> Self {
+        Self::new(
+            CollisionLayers::HOSTILE_PROJECTILE,
+            CollisionLayers::PLAYER.union(CollisionLayers::MECH),
+        )
+    }
+
+    pub const fn pickup() -> Self {
+        Self::new(CollisionLayers::PICKUP, CollisionLayers::PLAYER.union(CollisionLayers::MECH))
+    }
+
+    pub const fn trigger() -> Self {
+        Self::new(
+            CollisionLayers::TRIGGER,
+            CollisionLayers::PLAYER
+                .union(CollisionLayers::ENEMY)
+                .union(CollisionLayers::MECH),
+        )
+    }
+
+    /// Rubble and other loose bits only ever hit the level
+    pub const fn debris() -> Self {
+        Self::new(CollisionLayers::DEBRIS, CollisionLayers::NONE)
+    }
+
+    pub fn interacts_with(&self, other: &CollisionFilter) -> bool {
+        self.mask.intersects(other.layer) && other.mask.intersects(self.layer)
+    }
+}
+
+impl Default for CollisionFilter {
+    /// Unfiltered: on every layer and reacting to every layer
+    fn default() -> Self {
+        Self::new(CollisionLayers::ALL, CollisionLayers::ALL)
+    }
+}
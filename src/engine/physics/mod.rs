@@ -1,3 +1,11 @@
+pub mod broadphase;
+pub mod character;
 pub mod collision;
+pub mod layers;
+pub mod separation;
 
+pub use broadphase::*;
+pub use character::*;
 pub use collision::*;
+pub use layers::*;
+pub use separation::*;
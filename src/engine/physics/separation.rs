@@ -0,0 +1,61 @@
+use crate::engine::core::Transform;
+use crate::engine::physics::{push_character, CharacterBody, RigidBody, SpatialHash};
+use crate::game::Level;
+use hecs::{Entity, World};
+
+/// Two solid bodies that were overlapping this tick
+#[derive(Debug, Clone, Copy)]
+pub struct BodyContact {
+    pub a: Entity,
+    pub b: Entity,
+    /// Horizontal direction from `a` towards `b` (-1 or 1)
+    pub direction: f32,
+}
+
+/// Push overlapping character bodies apart sideways, heavier bodies giving way less,
+/// and return every contact so gameplay systems can react to them (e.g. contact damage)
+pub fn entity_collision_system(world: &mut World, level: &Level, broadphase: &SpatialHash) -> Vec<BodyContact> {
+    let mut contacts = Vec::new();
+
+    for (a, b) in broadphase.pairs() {
+        let (Some(entry_a), Some(entry_b)) = (broadphase.get(a), broadphase.get(b)) else {
+            continue;
+        };
+        let [Ok((character_a, transform_a, body_a)), Ok((character_b, transform_b, body_b))] =
+            world.query_many_mut::<(&CharacterBody, &mut Transform, &mut RigidBody), 2>([a, b])
+        else {
+            continue;
+        };
+        if !character_a.enabled || !character_b.enabled {
+            continue;
+        }
+
+        let bounds_a = entry_a.bounds;
+        let bounds_b = entry_b.bounds;
+        let overlap = (bounds_a.x + bounds_a.width).min(bounds_b.x + bounds_b.width) - bounds_a.x.max(bounds_b.x);
+        if overlap <= 0.0 {
+            continue;
+        }
+
+        // Split the separation by mass - a mech barely moves, the enemy it walks into gets shoved aside
+        let difference = entry_b.position.x - entry_a.position.x;
+        let direction = if difference != 0.0 { difference.signum() } else if a < b { 1.0 } else { -1.0 };
+        let total_mass = body_a.mass + body_b.mass;
+        let share_a = body_b.mass / total_mass;
+        let share_b = body_a.mass / total_mass;
+        push_character(character_a, &mut transform_a.position, -direction * overlap * share_a, level);
+        push_character(character_b, &mut transform_b.position, direction * overlap * share_b, level);
+
+        // Bodies moving into each other end up moving together, keeping their combined momentum
+        let closing = (body_a.velocity.x - body_b.velocity.x) * direction;
+        if closing > 0.0 {
+            let shared = (body_a.velocity.x * body_a.mass + body_b.velocity.x * body_b.mass) / total_mass;
+            body_a.velocity.x = shared;
+            body_b.velocity.x = shared;
+        }
+
+        contacts.push(BodyContact { a, b, direction });
+    }
+
+    contacts
+}
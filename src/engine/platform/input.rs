@@ -102,3 +102,9 @@ impl InputState {
         self.mouse_delta
     }
 }
+
+impl Default for InputState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
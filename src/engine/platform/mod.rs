@@ -12,6 +12,10 @@ pub struct Platform {
     pub window: Window,
     pub event_pump: EventPump,
     pub input: InputState,
+    /// New window size in pixels if the window was resized this frame
+    pub resized: Option<(u32, u32)>,
+    /// The driver threw away render target contents (or the whole device) this frame
+    pub render_targets_reset: bool,
 }
 
 impl Platform {
@@ -26,18 +30,31 @@ impl Platform {
             window,
             event_pump,
             input,
+            resized: None,
+            render_targets_reset: false,
         })
     }
 
     pub fn handle_events(&mut self, running: &mut bool) -> Result<()> {
-        use sdl2::event::Event;
+        use sdl2::event::{Event, WindowEvent};
         use sdl2::keyboard::Keycode;
 
         self.input.update();
+        self.resized = None;
+        self.render_targets_reset = false;
 
         for event in self.event_pump.poll_iter() {
             match event {
                 Event::Quit { .. } => *running = false,
+                Event::Window {
+                    win_event: WindowEvent::SizeChanged(width, height),
+                    ..
+                } => {
+                    self.resized = Some((width.max(1) as u32, height.max(1) as u32));
+                }
+                Event::RenderTargetsReset { .. } | Event::RenderDeviceReset { .. } => {
+                    self.render_targets_reset = true;
+                }
                 Event::KeyDown {
                     keycode: Some(Keycode::Escape),
                     ..
@@ -0,0 +1,176 @@
+use crate::engine::rendering::{Sprite, SpriteSheet};
+use std::collections::HashMap;
+
+/// A run of sprite sheet frames played at a fixed rate
+#[derive(Debug, Clone)]
+pub struct AnimationClip {
+    /// Sheet frame indices in play order
+    pub frames: Vec<u32>,
+    pub fps: f32,
+    pub looping: bool,
+    /// Named events fired when the clip reaches a position in `frames`
+    pub events: Vec<(usize, &'static str)>,
+}
+
+impl AnimationClip {
+    /// Looping clip over consecutive sheet frames
+    pub fn new(first_frame: u32, frame_count: u32, fps: f32) -> Self {
+        Self {
+            frames: (first_frame..first_frame + frame_count.max(1)).collect(),
+            fps,
+            looping: true,
+            events: Vec::new(),
+        }
+    }
+
+    /// Play through once and hold the last frame
+    pub fn once(mut self) -> Self {
+        self.looping = false;
+        self
+    }
+
+    pub fn with_event(mut self, position: usize, name: &'static str) -> Self {
+        self.events.push((position, name));
+        self
+    }
+
+    /// Seconds for one pass through the clip
+    pub fn duration(&self) -> f32 {
+        self.frames.len() as f32 / self.fps.max(0.001)
+    }
+}
+
+/// An animation event raised by an entity's `Animator`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationEvent {
+    pub entity: hecs::Entity,
+    pub name: &'static str,
+}
+
+/// Plays named clips and tracks which one is current.
+/// Systems pick the state with `play`; `update` advances frames and reports events.
+#[derive(Debug, Clone)]
+pub struct Animator {
+    pub sheet: Option<SpriteSheet>,
+    clips: HashMap<&'static str, AnimationClip>,
+    state: &'static str,
+    /// Position within the current clip's frames
+    position: usize,
+    timer: f32,
+    finished: bool,
+    /// Whether the current position's events have been raised yet
+    entered: bool,
+}
+
+impl Animator {
+    pub fn new(initial_state: &'static str) -> Self {
+        Self {
+            sheet: None,
+            clips: HashMap::new(),
+            state: initial_state,
+            position: 0,
+            timer: 0.0,
+            finished: false,
+            entered: false,
+        }
+    }
+
+    pub fn with_sheet(mut self, sheet: Option<SpriteSheet>) -> Self {
+        self.sheet = sheet;
+        self
+    }
+
+    pub fn with_clip(mut self, state: &'static str, clip: AnimationClip) -> Self {
+        self.clips.insert(state, clip);
+        self
+    }
+
+    pub fn state(&self) -> &'static str {
+        self.state
+    }
+
+    /// Switch to a state, restarting its clip. Staying in the current state does nothing.
+    pub fn play(&mut self, state: &'static str) {
+        if state != self.state && self.clips.contains_key(state) {
+            self.restart(state);
+        }
+    }
+
+    /// Switch to a state even if it's already playing
+    pub fn restart(&mut self, state: &'static str) {
+        self.state = state;
+        self.position = 0;
+        self.timer = 0.0;
+        self.finished = false;
+        self.entered = false;
+    }
+
+    /// A one-shot clip is still running and shouldn't be interrupted by ordinary state changes
+    pub fn is_busy(&self) -> bool {
+        self.clips.get(self.state).is_some_and(|clip| !clip.looping) && !self.finished
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Sheet frame to draw right now
+    pub fn current_frame(&self) -> u32 {
+        self.clips
+            .get(self.state)
+            .and_then(|clip| clip.frames.get(self.position))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Advance the current clip, returning the names of any events it passed
+    pub fn update(&mut self, delta_time: f32) -> Vec<&'static str> {
+        let mut events = Vec::new();
+        let Some(clip) = self.clips.get(self.state) else {
+            return events;
+        };
+
+        if !self.entered {
+            self.entered = true;
+            events.extend(events_at(clip, self.position));
+        }
+        if self.finished {
+            return events;
+        }
+
+        self.timer += delta_time;
+        let frame_time = 1.0 / clip.fps.max(0.001);
+        while self.timer >= frame_time {
+            self.timer -= frame_time;
+            if self.position + 1 < clip.frames.len() {
+                self.position += 1;
+            } else if clip.looping {
+                self.position = 0;
+            } else {
+                self.finished = true;
+                self.timer = 0.0;
+                break;
+            }
+            events.extend(events_at(clip, self.position));
+        }
+        events
+    }
+}
+
+/// Advances every animator, points its sprite at the current sheet frame and collects the events raised
+pub fn animation_system(world: &mut hecs::World, delta_time: f32) -> Vec<AnimationEvent> {
+    let mut raised = Vec::new();
+    for (entity, (animator, sprite)) in world.query_mut::<(&mut Animator, &mut Sprite)>() {
+        for name in animator.update(delta_time) {
+            raised.push(AnimationEvent { entity, name });
+        }
+        if let Some(sheet) = &animator.sheet {
+            sprite.source_rect = Some(sheet.frame_rect(animator.current_frame()));
+        }
+    }
+    raised
+}
+
+fn events_at(clip: &AnimationClip, position: usize) -> impl Iterator<Item = &'static str> + '_ {
+    clip.events.iter().filter(move |(at, _)| *at == position).map(|(_, name)| *name)
+}
@@ -0,0 +1,211 @@
+use crate::engine::core::{Color, Rect};
+use crate::engine::rendering::Sprite;
+use anyhow::{anyhow, bail, Context, Result};
+use glam::Vec2;
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::render::{BlendMode, Texture, TextureCreator};
+use sdl2::video::WindowContext;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where the game looks for its sprite sheet list
+pub const SPRITE_MANIFEST_PATH: &str = "assets/sprites/sheets.ron";
+
+/// Cheap reference to a texture owned by the `AssetManager`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextureHandle(usize);
+
+/// A texture cut into equally sized frames, read left to right, top to bottom
+#[derive(Debug, Clone)]
+pub struct SpriteSheet {
+    pub texture: TextureHandle,
+    pub frame_size: Vec2,
+    pub columns: u32,
+    pub rows: u32,
+}
+
+impl SpriteSheet {
+    pub fn frame_count(&self) -> u32 {
+        self.columns * self.rows
+    }
+
+    /// Source rectangle of a frame, wrapping past the last one
+    pub fn frame_rect(&self, index: u32) -> Rect {
+        let index = index % self.frame_count().max(1);
+        let column = index % self.columns.max(1);
+        let row = index / self.columns.max(1);
+        Rect::new(
+            column as f32 * self.frame_size.x,
+            row as f32 * self.frame_size.y,
+            self.frame_size.x,
+            self.frame_size.y,
+        )
+    }
+
+    /// Point a sprite at the first frame of this sheet, untinted
+    pub fn apply(&self, sprite: &mut Sprite) {
+        sprite.texture = Some(self.texture);
+        sprite.source_rect = Some(self.frame_rect(0));
+        sprite.color = Color::WHITE;
+    }
+}
+
+/// One entry of the sprite manifest
+#[derive(Debug, Clone, Deserialize)]
+pub struct SheetDefinition {
+    pub name: String,
+    /// PNG path, relative to the manifest
+    pub path: String,
+    pub frame_width: u32,
+    pub frame_height: u32,
+}
+
+/// Decoded 8-bit RGBA pixels
+pub struct ImageData {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+/// Decode a PNG of any color type into 8-bit RGBA
+pub fn decode_png(path: &Path) -> Result<ImageData> {
+    let file = fs::File::open(path).with_context(|| format!("opening {}", path.display()))?;
+    let mut decoder = png::Decoder::new(file);
+    // Palettes become RGB(A) and 16-bit channels become 8-bit
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().with_context(|| format!("reading {}", path.display()))?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer)?;
+    buffer.truncate(info.buffer_size());
+
+    let pixels = match info.color_type {
+        png::ColorType::Rgba => buffer,
+        png::ColorType::Rgb => buffer.chunks_exact(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha => buffer.chunks_exact(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        png::ColorType::Grayscale => buffer.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        png::ColorType::Indexed => bail!("{}: palette was not expanded", path.display()),
+    };
+
+    Ok(ImageData {
+        width: info.width,
+        height: info.height,
+        pixels,
+    })
+}
+
+/// Loads PNG textures once and hands out handles to them.
+/// Sprites whose texture is missing keep drawing as the colored placeholders from `MockAssetGenerator`.
+pub struct AssetManager {
+    texture_creator: TextureCreator<WindowContext>,
+    textures: Vec<Texture>,
+    by_path: HashMap<PathBuf, TextureHandle>,
+    sheets: HashMap<String, SpriteSheet>,
+}
+
+impl AssetManager {
+    pub fn new(texture_creator: TextureCreator<WindowContext>) -> Self {
+        Self {
+            texture_creator,
+            textures: Vec::new(),
+            by_path: HashMap::new(),
+            sheets: HashMap::new(),
+        }
+    }
+
+    /// Load a PNG as a texture, or return the cached handle if it's already loaded
+    pub fn load_texture(&mut self, path: impl AsRef<Path>) -> Result<TextureHandle> {
+        let path = path.as_ref();
+        if let Some(&handle) = self.by_path.get(path) {
+            return Ok(handle);
+        }
+
+        let image = decode_png(path)?;
+        let mut texture = self
+            .texture_creator
+            .create_texture_static(PixelFormatEnum::RGBA32, image.width, image.height)
+            .map_err(|e| anyhow!("creating texture for {}: {}", path.display(), e))?;
+        texture
+            .update(None, &image.pixels, image.width as usize * 4)
+            .map_err(|e| anyhow!("uploading {}: {}", path.display(), e))?;
+        texture.set_blend_mode(BlendMode::Blend);
+
+        let handle = TextureHandle(self.textures.len());
+        self.textures.push(texture);
+        self.by_path.insert(path.to_path_buf(), handle);
+        Ok(handle)
+    }
+
+    /// A blank, transparent texture that can be drawn into, e.g. to cache static geometry
+    pub fn create_target_texture(&mut self, width: u32, height: u32) -> Result<TextureHandle> {
+        let mut texture = self
+            .texture_creator
+            .create_texture_target(PixelFormatEnum::ARGB8888, width, height)
+            .map_err(|e| anyhow!("creating {}x{} render target: {}", width, height, e))?;
+        texture.set_blend_mode(BlendMode::Blend);
+
+        let handle = TextureHandle(self.textures.len());
+        self.textures.push(texture);
+        Ok(handle)
+    }
+
+    /// Load a PNG and register it as a sheet of `frame_size` frames under `name`
+    pub fn load_sheet(&mut self, name: &str, path: impl AsRef<Path>, frame_size: Vec2) -> Result<SpriteSheet> {
+        let texture = self.load_texture(path)?;
+        let query = self.textures[texture.0].query();
+        let sheet = SpriteSheet {
+            texture,
+            frame_size,
+            columns: (query.width as f32 / frame_size.x).floor().max(1.0) as u32,
+            rows: (query.height as f32 / frame_size.y).floor().max(1.0) as u32,
+        };
+        self.sheets.insert(name.to_string(), sheet.clone());
+        Ok(sheet)
+    }
+
+    /// Load every sheet listed in a RON manifest, skipping (and logging) any that fail.
+    /// A missing manifest just means no sheets; returns how many loaded.
+    pub fn load_manifest(&mut self, path: impl AsRef<Path>) -> Result<usize> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(0);
+        }
+        let contents = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let definitions: Vec<SheetDefinition> =
+            ron::from_str(&contents).with_context(|| format!("parsing {}", path.display()))?;
+        let base = path.parent().unwrap_or(Path::new("."));
+
+        let mut loaded = 0;
+        for definition in definitions {
+            let frame_size = Vec2::new(definition.frame_width as f32, definition.frame_height as f32);
+            match self.load_sheet(&definition.name, base.join(&definition.path), frame_size) {
+                Ok(_) => loaded += 1,
+                Err(e) => log::warn!("Sprite sheet {} unavailable, using placeholder ({:#})", definition.name, e),
+            }
+        }
+        Ok(loaded)
+    }
+
+    pub fn texture(&self, handle: TextureHandle) -> Option<&Texture> {
+        self.textures.get(handle.0)
+    }
+
+    pub fn texture_mut(&mut self, handle: TextureHandle) -> Option<&mut Texture> {
+        self.textures.get_mut(handle.0)
+    }
+
+    pub fn sheet(&self, name: &str) -> Option<&SpriteSheet> {
+        self.sheets.get(name)
+    }
+}
+
+impl Drop for AssetManager {
+    fn drop(&mut self) {
+        for texture in self.textures.drain(..) {
+            // SAFETY: the renderer that created these outlives the asset manager (it owns it)
+            // and nothing else holds the textures once they're drained
+            unsafe { texture.destroy() };
+        }
+    }
+}
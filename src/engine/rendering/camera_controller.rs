@@ -0,0 +1,195 @@
+use crate::engine::core::Rect;
+use crate::engine::rendering::Camera;
+use glam::Vec2;
+
+/// Largest shake offset at full trauma, in world pixels
+const MAX_SHAKE_OFFSET: f32 = 24.0;
+/// Trauma lost per second
+const TRAUMA_DECAY: f32 = 1.4;
+/// World pixels kept around framed targets
+const FRAMING_MARGIN: f32 = 160.0;
+
+/// Points the camera should keep on screen together with its target for a while
+#[derive(Debug, Clone)]
+struct Framing {
+    points: Vec<Vec2>,
+    timer: f32,
+}
+
+/// Drives a `Camera`: follows a target through a dead zone with velocity look-ahead,
+/// eases zoom, keeps the view inside the level and applies trauma-based shake.
+#[derive(Debug, Clone)]
+pub struct CameraController {
+    /// Half-size of the box the target can move in without dragging the camera
+    pub dead_zone: Vec2,
+    /// Seconds of target velocity to lead by
+    pub look_ahead_time: f32,
+    pub max_look_ahead: Vec2,
+    /// How quickly the camera catches up with where it wants to be (per second)
+    pub follow_speed: f32,
+    pub zoom_speed: f32,
+    /// World area the view may never leave
+    pub bounds: Option<Rect>,
+    focus: Vec2,
+    look_ahead: Vec2,
+    target_zoom: f32,
+    trauma: f32,
+    shake_time: f32,
+    framing: Option<Framing>,
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self {
+            dead_zone: Vec2::new(48.0, 64.0),
+            look_ahead_time: 0.35,
+            max_look_ahead: Vec2::new(160.0, 60.0),
+            follow_speed: 5.0,
+            zoom_speed: 3.0,
+            bounds: None,
+            focus: Vec2::ZERO,
+            look_ahead: Vec2::ZERO,
+            target_zoom: 1.0,
+            trauma: 0.0,
+            shake_time: 0.0,
+            framing: None,
+        }
+    }
+}
+
+impl CameraController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_dead_zone(mut self, half_size: Vec2) -> Self {
+        self.dead_zone = half_size;
+        self
+    }
+
+    pub fn with_look_ahead(mut self, seconds: f32, max: Vec2) -> Self {
+        self.look_ahead_time = seconds;
+        self.max_look_ahead = max;
+        self
+    }
+
+    pub fn set_bounds(&mut self, bounds: Rect) {
+        self.bounds = Some(bounds);
+    }
+
+    pub fn set_target_zoom(&mut self, zoom: f32) {
+        self.target_zoom = zoom.clamp(0.1, 5.0);
+    }
+
+    pub fn target_zoom(&self) -> f32 {
+        self.target_zoom
+    }
+
+    /// Add screen shake. Trauma is capped at 1 and the shake grows with its square, so small knocks stay subtle.
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    /// Keep `points` in view alongside the followed target for `duration` seconds, zooming out if needed.
+    /// Call every frame with a short duration to track moving targets.
+    pub fn frame(&mut self, points: Vec<Vec2>, duration: f32) {
+        self.framing = Some(Framing { points, timer: duration });
+    }
+
+    pub fn is_framing(&self) -> bool {
+        self.framing.is_some()
+    }
+
+    /// Jump straight to a position, e.g. on spawning, with no easing, look-ahead or shake
+    pub fn snap_to(&mut self, camera: &mut Camera, position: Vec2) {
+        self.focus = position;
+        self.look_ahead = Vec2::ZERO;
+        self.trauma = 0.0;
+        self.framing = None;
+        camera.zoom = self.target_zoom;
+        camera.position = self.clamp_to_bounds(position, camera);
+        camera.shake_offset = Vec2::ZERO;
+    }
+
+    /// Move the camera towards `target`, which is moving at `velocity`
+    pub fn update(&mut self, camera: &mut Camera, target: Vec2, velocity: Vec2, delta_time: f32) {
+        // Exponential easing so the feel doesn't depend on frame rate
+        let follow = 1.0 - (-self.follow_speed * delta_time).exp();
+        let zoom_ease = 1.0 - (-self.zoom_speed * delta_time).exp();
+
+        // The focus only moves once the target pushes against the edge of the dead zone
+        let offset = target - self.focus;
+        let excess = offset - offset.clamp(-self.dead_zone, self.dead_zone);
+        self.focus += excess;
+
+        let lead = (velocity * self.look_ahead_time).clamp(-self.max_look_ahead, self.max_look_ahead);
+        self.look_ahead += (lead - self.look_ahead) * follow;
+
+        let mut desired = self.focus + self.look_ahead;
+        let mut zoom = self.target_zoom;
+        if let Some(framing) = &mut self.framing {
+            framing.timer -= delta_time;
+            let (min, max) = framing
+                .points
+                .iter()
+                .fold((target, target), |(min, max), &point| (min.min(point), max.max(point)));
+            desired = (min + max) / 2.0;
+            let needed = (max - min) + Vec2::splat(FRAMING_MARGIN * 2.0);
+            let fit = (camera.viewport_size / needed).min_element();
+            zoom = zoom.min(fit);
+        }
+        if self.framing.as_ref().is_some_and(|framing| framing.timer <= 0.0) {
+            self.framing = None;
+        }
+
+        camera.zoom += (zoom - camera.zoom) * zoom_ease;
+        let position = camera.position + (desired - camera.position) * follow;
+        let position = self.clamp_to_bounds(position, camera);
+
+        // Round camera position for pixel-perfect rendering to prevent sub-pixel movement
+        camera.position = if camera.pixel_perfect {
+            (position * camera.zoom).round() / camera.zoom
+        } else {
+            position
+        };
+
+        self.trauma = (self.trauma - TRAUMA_DECAY * delta_time).max(0.0);
+        self.shake_time += delta_time;
+        camera.shake_offset = self.shake_offset();
+    }
+
+    /// Smooth pseudo-noise from a few out-of-step sine waves, scaled by trauma squared
+    fn shake_offset(&self) -> Vec2 {
+        let amount = MAX_SHAKE_OFFSET * self.trauma * self.trauma;
+        if amount <= 0.0 {
+            return Vec2::ZERO;
+        }
+        let t = self.shake_time * 30.0;
+        let x = (t * 1.3).sin() * 0.6 + (t * 2.9 + 1.7).sin() * 0.4;
+        let y = (t * 1.7 + 0.5).sin() * 0.6 + (t * 3.1 + 2.3).sin() * 0.4;
+        Vec2::new(x, y) * amount
+    }
+
+    /// Slide a camera centre so the whole view stays inside the bounds, centring on any axis the view is wider than
+    fn clamp_to_bounds(&self, position: Vec2, camera: &Camera) -> Vec2 {
+        let Some(bounds) = self.bounds else {
+            return position;
+        };
+        let half_view = camera.viewport_size / camera.zoom / 2.0;
+        let clamp_axis = |value: f32, start: f32, length: f32, half: f32| {
+            if length <= half * 2.0 {
+                start + length / 2.0
+            } else {
+                value.clamp(start + half, start + length - half)
+            }
+        };
+        Vec2::new(
+            clamp_axis(position.x, bounds.x, bounds.width, half_view.x),
+            clamp_axis(position.y, bounds.y, bounds.height, half_view.y),
+        )
+    }
+}
@@ -0,0 +1,146 @@
+use crate::engine::rendering::ImageData;
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+
+/// Where screenshots and capture sequences are written
+pub const SCREENSHOT_DIR: &str = "screenshots";
+
+/// Local time, down to the millisecond, so captures sort by when they were taken
+fn timestamp() -> String {
+    chrono::Local::now().format("%Y-%m-%d_%H-%M-%S-%3f").to_string()
+}
+
+/// Every Nth frame saved into its own folder, for turning into GIFs
+#[derive(Debug, Clone)]
+struct CaptureSequence {
+    dir: PathBuf,
+    every: u32,
+    frame: u32,
+    saved: u32,
+}
+
+/// Decides which presented frames get written to disk
+#[derive(Debug, Clone, Default)]
+pub struct FrameCapture {
+    screenshot: Option<PathBuf>,
+    sequence: Option<CaptureSequence>,
+}
+
+impl FrameCapture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Save the next presented frame and return where it will go
+    pub fn request_screenshot(&mut self) -> PathBuf {
+        let path = Path::new(SCREENSHOT_DIR).join(format!("screenshot_{}.png", timestamp()));
+        self.screenshot = Some(path.clone());
+        path
+    }
+
+    /// Start saving every `every`th frame and return the folder they go into
+    pub fn start_sequence(&mut self, every: u32) -> PathBuf {
+        let dir = Path::new(SCREENSHOT_DIR).join(format!("sequence_{}", timestamp()));
+        self.sequence = Some(CaptureSequence {
+            dir: dir.clone(),
+            every: every.max(1),
+            frame: 0,
+            saved: 0,
+        });
+        dir
+    }
+
+    /// Stop a running sequence and return how many frames it saved
+    pub fn stop_sequence(&mut self) -> Option<u32> {
+        self.sequence.take().map(|sequence| sequence.saved)
+    }
+
+    /// Called once per presented frame: where to save it, if anywhere
+    pub fn next_frame_path(&mut self) -> Option<PathBuf> {
+        if let Some(path) = self.screenshot.take() {
+            return Some(path);
+        }
+        let sequence = self.sequence.as_mut()?;
+        let due = sequence.frame % sequence.every == 0;
+        sequence.frame += 1;
+        if !due {
+            return None;
+        }
+        sequence.saved += 1;
+        Some(sequence.dir.join(format!("frame_{:05}.png", sequence.saved)))
+    }
+}
+
+/// Encode 8-bit RGBA pixels as a PNG, creating the folder if needed
+pub fn write_png(path: &Path, image: &ImageData) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+    }
+    let file = fs::File::create(path).with_context(|| format!("creating {}", path.display()))?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), image.width, image.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer
+        .write_image_data(&image.pixels)
+        .with_context(|| format!("writing {}", path.display()))?;
+    Ok(())
+}
+
+/// A frame waiting to be written, and where to
+type QueuedFrame = (PathBuf, ImageData);
+
+/// Frames allowed to wait for the worker; past this they're dropped rather than piling up in memory
+const MAX_QUEUED_FRAMES: usize = 8;
+
+/// Writes captured frames on one worker thread so recording doesn't stall the game loop.
+/// The thread starts with the first frame and finishes the queue when the writer is dropped.
+/// If the disk can't keep up, frames that don't fit in the queue are skipped.
+#[derive(Default)]
+pub struct PngWriter {
+    worker: Option<(SyncSender<QueuedFrame>, JoinHandle<()>)>,
+}
+
+impl PngWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a frame to be saved at `path`
+    pub fn write(&mut self, path: PathBuf, image: ImageData) {
+        let (sender, _) = self.worker.get_or_insert_with(|| {
+            let (sender, receiver) = mpsc::sync_channel::<QueuedFrame>(MAX_QUEUED_FRAMES);
+            let handle = thread::spawn(move || {
+                for (path, image) in receiver {
+                    if let Err(e) = write_png(&path, &image) {
+                        log::warn!("Failed to save capture: {:#}", e);
+                    }
+                }
+            });
+            (sender, handle)
+        });
+        match sender.try_send((path, image)) {
+            Ok(()) => {}
+            Err(TrySendError::Full((path, _))) => {
+                log::warn!("Capture queue is full, skipped {}", path.display());
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                log::warn!("Capture writer has stopped, frame not saved");
+            }
+        }
+    }
+}
+
+impl Drop for PngWriter {
+    fn drop(&mut self) {
+        if let Some((sender, handle)) = self.worker.take() {
+            // Closing the channel lets the worker drain what's queued and exit
+            drop(sender);
+            let _ = handle.join();
+        }
+    }
+}
@@ -14,10 +14,12 @@ pub enum RenderLayer {
     Gameplay = 3,
     /// Entities (player, enemies, items)
     Entities = 4,
+    /// Particles, health bars and markers drawn over entities
+    Effects = 5,
     /// Foreground decorations
-    Foreground = 5,
+    Foreground = 6,
     /// UI and overlay elements
-    UI = 6,
+    UI = 7,
 }
 
 impl Default for RenderLayer {
@@ -57,6 +59,21 @@ impl ParallaxConfig {
         self.auto_scroll_y = y;
         self
     }
+
+    /// Where a world position should be drawn so it scrolls at this layer's rate
+    pub fn apply(&self, world_pos: Vec2, camera_offset: Vec2, elapsed_time: f32) -> Vec2 {
+        let parallax_offset = Vec2::new(
+            camera_offset.x * (1.0 - self.scroll_factor_x),
+            camera_offset.y * (1.0 - self.scroll_factor_y),
+        );
+
+        let auto_scroll = Vec2::new(
+            self.auto_scroll_x * elapsed_time,
+            self.auto_scroll_y * elapsed_time,
+        );
+
+        world_pos + parallax_offset + auto_scroll
+    }
 }
 
 impl RenderLayer {
@@ -68,6 +85,7 @@ impl RenderLayer {
             Self::NearBackground => ParallaxConfig::new(0.6, 0.5),
             Self::Gameplay => ParallaxConfig::new(1.0, 1.0),
             Self::Entities => ParallaxConfig::new(1.0, 1.0),
+            Self::Effects => ParallaxConfig::new(1.0, 1.0),
             Self::Foreground => ParallaxConfig::new(1.2, 1.1),
             Self::UI => ParallaxConfig::static_layer(),
         }
@@ -138,18 +156,6 @@ impl LayeredSprite {
 
     /// Calculate rendered position with parallax effect
     pub fn calculate_position(&self, world_pos: Vec2, camera_offset: Vec2, elapsed_time: f32) -> Vec2 {
-        let parallax = self.get_parallax();
-        
-        let parallax_offset = Vec2::new(
-            camera_offset.x * (1.0 - parallax.scroll_factor_x),
-            camera_offset.y * (1.0 - parallax.scroll_factor_y),
-        );
-        
-        let auto_scroll = Vec2::new(
-            parallax.auto_scroll_x * elapsed_time,
-            parallax.auto_scroll_y * elapsed_time,
-        );
-        
-        world_pos + parallax_offset + auto_scroll
+        self.get_parallax().apply(world_pos, camera_offset, elapsed_time)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,172 @@
+use crate::engine::core::Color;
+use crate::engine::rendering::Renderer;
+use glam::Vec2;
+
+/// Screen pixels per light map cell. Coarse enough to shade on the CPU every frame.
+pub const LIGHT_CELL_SIZE: f32 = 16.0;
+
+/// Limits a light to a wedge, like a headlight
+#[derive(Debug, Clone, Copy)]
+pub struct LightCone {
+    pub direction: Vec2,
+    /// Half the opening angle in radians
+    pub half_angle: f32,
+}
+
+/// A light attached to an entity, centred on its transform plus `offset`
+#[derive(Debug, Clone, Copy)]
+pub struct PointLight {
+    pub radius: f32,
+    pub color: Color,
+    /// 1.0 lights its centre fully; higher pushes the bright core outwards
+    pub intensity: f32,
+    pub offset: Vec2,
+    pub cone: Option<LightCone>,
+    /// Solid tiles between the light and a spot leave it in shadow
+    pub casts_shadows: bool,
+}
+
+impl PointLight {
+    pub fn new(radius: f32, color: Color) -> Self {
+        Self {
+            radius,
+            color,
+            intensity: 1.0,
+            offset: Vec2::ZERO,
+            cone: None,
+            casts_shadows: true,
+        }
+    }
+
+    pub fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: Vec2) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_cone(mut self, direction: Vec2, half_angle: f32) -> Self {
+        self.cone = Some(LightCone { direction, half_angle });
+        self
+    }
+
+    pub fn without_shadows(mut self) -> Self {
+        self.casts_shadows = false;
+        self
+    }
+}
+
+/// Short-lived light that fades out, then its entity is removed
+#[derive(Debug, Clone, Copy)]
+pub struct LightFlash {
+    pub timer: f32,
+    pub duration: f32,
+    /// Intensity at the start of the flash
+    pub peak: f32,
+}
+
+impl LightFlash {
+    pub fn new(duration: f32, peak: f32) -> Self {
+        Self { timer: duration, duration, peak }
+    }
+}
+
+/// Collects a frame's lights and darkens the screen around them.
+/// Each cell gets the ambient light plus every light reaching it, then the grid is multiplied over the frame.
+#[derive(Debug, Default)]
+pub struct LightMap {
+    lights: Vec<(Vec2, PointLight)>,
+    cells: Vec<[f32; 3]>,
+}
+
+impl LightMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a light at a world position (already offset)
+    pub fn add(&mut self, position: Vec2, light: PointLight) {
+        self.lights.push((position, light));
+    }
+
+    pub fn light_count(&self) -> usize {
+        self.lights.len()
+    }
+
+    /// Shade and composite the light map, then forget this frame's lights.
+    /// `blocked(light, point)` reports whether level geometry stands between two world positions.
+    pub fn render(&mut self, renderer: &mut Renderer, ambient: Color, mut blocked: impl FnMut(Vec2, Vec2) -> bool) {
+        let lights = std::mem::take(&mut self.lights);
+        // Lights can only brighten what the ambient darkens, so broad daylight needs no pass
+        if ambient.r == 255 && ambient.g == 255 && ambient.b == 255 {
+            return;
+        }
+
+        let camera = &renderer.camera;
+        let columns = (camera.viewport_size.x / LIGHT_CELL_SIZE).ceil() as usize;
+        let rows = (camera.viewport_size.y / LIGHT_CELL_SIZE).ceil() as usize;
+        let base = [ambient.r as f32 / 255.0, ambient.g as f32 / 255.0, ambient.b as f32 / 255.0];
+        self.cells.clear();
+        self.cells.resize(columns * rows, base);
+
+        for (position, light) in &lights {
+            let center = camera.world_to_screen(*position);
+            let radius = light.radius * camera.zoom;
+            if radius <= 0.0
+                || center.x + radius < 0.0
+                || center.y + radius < 0.0
+                || center.x - radius > camera.viewport_size.x
+                || center.y - radius > camera.viewport_size.y
+            {
+                continue;
+            }
+
+            let min_column = ((center.x - radius) / LIGHT_CELL_SIZE).floor().max(0.0) as usize;
+            let max_column = (((center.x + radius) / LIGHT_CELL_SIZE).ceil() as usize).min(columns);
+            let min_row = ((center.y - radius) / LIGHT_CELL_SIZE).floor().max(0.0) as usize;
+            let max_row = (((center.y + radius) / LIGHT_CELL_SIZE).ceil() as usize).min(rows);
+            let color = [light.color.r as f32 / 255.0, light.color.g as f32 / 255.0, light.color.b as f32 / 255.0];
+
+            for row in min_row..max_row {
+                for column in min_column..max_column {
+                    let cell = Vec2::new((column as f32 + 0.5) * LIGHT_CELL_SIZE, (row as f32 + 0.5) * LIGHT_CELL_SIZE);
+                    let to_cell = cell - center;
+                    let distance = to_cell.length() / radius;
+                    if distance >= 1.0 {
+                        continue;
+                    }
+
+                    let mut strength = (1.0 - distance) * (1.0 - distance) * light.intensity;
+                    if let Some(cone) = light.cone {
+                        let angle = if to_cell == Vec2::ZERO { 0.0 } else { cone.direction.angle_between(to_cell).abs() };
+                        if angle > cone.half_angle {
+                            continue;
+                        }
+                        // Soften towards the edges of the beam
+                        strength *= 1.0 - (angle / cone.half_angle.max(0.001)).powi(2) * 0.5;
+                    }
+                    if light.casts_shadows && blocked(*position, camera.screen_to_world(cell)) {
+                        continue;
+                    }
+
+                    for (value, channel) in self.cells[row * columns + column].iter_mut().zip(color) {
+                        *value += channel * strength;
+                    }
+                }
+            }
+        }
+
+        let shades: Vec<Color> = self
+            .cells
+            .iter()
+            .map(|[r, g, b]| {
+                let channel = |value: f32| (value.min(1.0) * 255.0) as u8;
+                Color::new(channel(*r), channel(*g), channel(*b), 255)
+            })
+            .collect();
+        renderer.draw_light_map(&shades, columns, LIGHT_CELL_SIZE);
+    }
+}
@@ -1,19 +1,39 @@
+pub mod animation;
+pub mod assets;
 pub mod camera;
+pub mod camera_controller;
+pub mod capture;
 pub mod layers;
+pub mod lighting;
 pub mod mock_assets;
+pub mod queue;
 pub mod sprite;
 
 use crate::engine::platform::Platform;
 use anyhow::Result;
+use glam::Vec2;
 
+pub use animation::*;
+pub use assets::*;
 pub use camera::*;
+pub use camera_controller::*;
+pub use capture::*;
 pub use layers::*;
+pub use lighting::*;
 pub use mock_assets::*;
+pub use queue::*;
 pub use sprite::*;
 
 pub struct Renderer {
+    /// Declared before the canvas so textures are destroyed while it still exists
+    pub assets: AssetManager,
     canvas: sdl2::render::Canvas<sdl2::video::Window>,
     pub camera: Camera,
+    /// Fixed size the frame is drawn at before being scaled up to the window
+    virtual_resolution: Option<(u32, u32)>,
+    /// Screenshots and capture sequences, saved as frames are presented
+    pub capture: FrameCapture,
+    capture_writer: PngWriter,
 }
 
 impl Renderer {
@@ -21,8 +41,47 @@ impl Renderer {
         let canvas = platform.window.canvas()?;
         let (width, height) = platform.window.size();
         let camera = Camera::new(width as f32, height as f32);
+        let assets = AssetManager::new(canvas.texture_creator());
 
-        Ok(Self { canvas, camera })
+        Ok(Self {
+            assets,
+            canvas,
+            camera,
+            virtual_resolution: None,
+            capture: FrameCapture::new(),
+            capture_writer: PngWriter::new(),
+        })
+    }
+
+    /// Size of the drawing area in pixels: the virtual resolution if one is set, otherwise the window
+    pub fn screen_size(&self) -> Vec2 {
+        self.camera.viewport_size
+    }
+
+    /// The window changed size. Without a virtual resolution the view grows or shrinks with it.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if self.virtual_resolution.is_none() {
+            self.camera.viewport_size = Vec2::new(width as f32, height as f32);
+        }
+    }
+
+    /// Draw at a fixed resolution and scale it to the window by whole multiples, letterboxing the rest.
+    /// Mouse positions are mapped back into the virtual resolution. `None` draws at the window's own size.
+    pub fn set_virtual_resolution(&mut self, resolution: Option<(u32, u32)>) -> Result<()> {
+        let (width, height) = match resolution {
+            Some(size) => size,
+            None => self.canvas.window().size(),
+        };
+        let (logical_width, logical_height) = resolution.unwrap_or((0, 0));
+        self.canvas.set_logical_size(logical_width, logical_height)?;
+        self.canvas.set_integer_scale(resolution.is_some()).map_err(|e| anyhow::anyhow!(e))?;
+        self.virtual_resolution = resolution;
+        self.camera.viewport_size = Vec2::new(width as f32, height as f32);
+        Ok(())
+    }
+
+    pub fn virtual_resolution(&self) -> Option<(u32, u32)> {
+        self.virtual_resolution
     }
 
     pub fn clear(&mut self, color: crate::engine::core::Color) {
@@ -32,7 +91,10 @@ impl Renderer {
 
     pub fn draw_sprite(&mut self, sprite: &Sprite, transform: &crate::engine::core::Transform) {
         let screen_pos = self.camera.world_to_screen(transform.position);
-        let size = sprite.size * transform.scale * self.camera.zoom;
+        // Negative scale mirrors the sprite on that axis
+        let size = sprite.size * transform.scale.abs() * self.camera.zoom;
+        let flip_x = sprite.flip_x != (transform.scale.x < 0.0);
+        let flip_y = sprite.flip_y != (transform.scale.y < 0.0);
 
         // Round positions to prevent sub-pixel gaps and add 1 pixel overlap for tiles
         let x = (screen_pos.x - size.x / 2.0).round() as i32;
@@ -42,9 +104,129 @@ impl Renderer {
         let height = size.y.ceil() as u32;
 
         let dest = sdl2::rect::Rect::new(x, y, width, height);
+        let rotated = transform.rotation.abs() > f32::EPSILON;
+        // Pivot relative to the sprite's top-left corner, in screen pixels
+        let pivot = match sprite.origin {
+            Some(origin) => origin * transform.scale.abs() * self.camera.zoom,
+            None => size / 2.0,
+        };
+
+        if let Some(texture) = sprite.texture.and_then(|handle| self.assets.texture_mut(handle)) {
+            let tint = sprite.color;
+            texture.set_color_mod(tint.r, tint.g, tint.b);
+            texture.set_alpha_mod(tint.a);
+            let source = sprite.source_rect.map(|rect| {
+                sdl2::rect::Rect::new(rect.x as i32, rect.y as i32, rect.width as u32, rect.height as u32)
+            });
+            let drawn = if rotated || flip_x || flip_y {
+                let center = sdl2::rect::Point::new(pivot.x.round() as i32, pivot.y.round() as i32);
+                let angle = transform.rotation.to_degrees() as f64;
+                self.canvas.copy_ex(texture, source, dest, angle, center, flip_x, flip_y)
+            } else {
+                self.canvas.copy(texture, source, dest)
+            };
+            if drawn.is_ok() {
+                return;
+            }
+        }
 
         self.canvas.set_draw_color(sprite.color.to_sdl());
-        let _ = self.canvas.fill_rect(dest);
+        if !rotated {
+            let _ = self.canvas.fill_rect(dest);
+            return;
+        }
+
+        // Solid sprites have no texture to rotate, so fill the rotated rectangle directly
+        let top_left = Vec2::new(x as f32, y as f32);
+        let pivot_screen = top_left + pivot;
+        let rotation = Vec2::from_angle(transform.rotation);
+        let corners = [
+            Vec2::ZERO,
+            Vec2::new(size.x, 0.0),
+            size,
+            Vec2::new(0.0, size.y),
+        ]
+        .map(|corner| pivot_screen + rotation.rotate(top_left + corner - pivot_screen));
+        self.fill_polygon(&corners);
+    }
+
+    /// Scanline fill of a convex polygon in screen space with the current draw color
+    fn fill_polygon(&mut self, points: &[Vec2]) {
+        let min_y = points.iter().map(|p| p.y).fold(f32::INFINITY, f32::min).floor() as i32;
+        let max_y = points.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max).ceil() as i32;
+
+        let mut spans = Vec::new();
+        for row in min_y..max_y {
+            // Sample through the middle of the pixel row
+            let scan_y = row as f32 + 0.5;
+            let mut left = f32::INFINITY;
+            let mut right = f32::NEG_INFINITY;
+            for (i, &a) in points.iter().enumerate() {
+                let b = points[(i + 1) % points.len()];
+                if (a.y <= scan_y) != (b.y <= scan_y) {
+                    let x = a.x + (scan_y - a.y) / (b.y - a.y) * (b.x - a.x);
+                    left = left.min(x);
+                    right = right.max(x);
+                }
+            }
+            if left < right {
+                let start = left.round() as i32;
+                let width = (right.round() as i32 - start).max(1) as u32;
+                spans.push(sdl2::rect::Rect::new(start, row, width, 1));
+            }
+        }
+        let _ = self.canvas.fill_rects(&spans);
+    }
+
+    /// Redraw a render-target texture (see `AssetManager::create_target_texture`) as solid rects
+    /// on a transparent background. Rects are in texture pixels.
+    pub fn fill_texture(&mut self, handle: TextureHandle, rects: &[(crate::engine::core::Rect, crate::engine::core::Color)]) -> Result<()> {
+        let texture = self
+            .assets
+            .texture_mut(handle)
+            .ok_or_else(|| anyhow::anyhow!("no texture for {:?}", handle))?;
+        let mut result = Ok(());
+        self.canvas
+            .with_texture_canvas(texture, |target| {
+                target.set_draw_color(sdl2::pixels::Color::RGBA(0, 0, 0, 0));
+                target.clear();
+                for (rect, color) in rects {
+                    let x = rect.x.round() as i32;
+                    let y = rect.y.round() as i32;
+                    let width = ((rect.x + rect.width).round() as i32 - x).max(1) as u32;
+                    let height = ((rect.y + rect.height).round() as i32 - y).max(1) as u32;
+                    target.set_draw_color(color.to_sdl());
+                    if let Err(e) = target.fill_rect(sdl2::rect::Rect::new(x, y, width, height)) {
+                        result = Err(anyhow::anyhow!(e));
+                    }
+                }
+            })
+            .map_err(|e| anyhow::anyhow!(e))?;
+        result
+    }
+
+    /// Multiply a grid of screen cells over the frame (white leaves it untouched), merging runs of equal cells
+    pub fn draw_light_map(&mut self, cells: &[crate::engine::core::Color], columns: usize, cell_size: f32) {
+        self.canvas.set_blend_mode(sdl2::render::BlendMode::Mod);
+        for (row, row_cells) in cells.chunks(columns.max(1)).enumerate() {
+            let mut start = 0;
+            while start < row_cells.len() {
+                let color = row_cells[start];
+                let rgb = (color.r, color.g, color.b);
+                let run = row_cells[start..].iter().take_while(|c| (c.r, c.g, c.b) == rgb).count();
+                if rgb != (255, 255, 255) {
+                    self.canvas.set_draw_color(color.to_sdl());
+                    let _ = self.canvas.fill_rect(sdl2::rect::Rect::new(
+                        (start as f32 * cell_size) as i32,
+                        (row as f32 * cell_size) as i32,
+                        (run as f32 * cell_size).ceil() as u32,
+                        cell_size.ceil() as u32,
+                    ));
+                }
+                start += run;
+            }
+        }
+        self.canvas.set_blend_mode(sdl2::render::BlendMode::None);
     }
 
     pub fn draw_ui_rect(&mut self, position: glam::Vec2, size: glam::Vec2, color: crate::engine::core::Color) {
@@ -99,7 +281,37 @@ impl Renderer {
         }
     }
 
+    /// Read back the frame drawn so far as RGBA pixels, at window resolution and without letterbox bars
+    pub fn read_frame(&self) -> Result<ImageData> {
+        let (output_width, output_height) = self.canvas.output_size().map_err(|e| anyhow::anyhow!(e))?;
+        // Integer scaling centres the frame at the largest whole multiple that fits, as SDL lays it out
+        let (x, y, width, height) = match self.virtual_resolution {
+            Some((width, height)) => {
+                let scale = (output_width / width).min(output_height / height).max(1);
+                let (width, height) = ((width * scale).min(output_width), (height * scale).min(output_height));
+                ((output_width - width) / 2, (output_height - height) / 2, width, height)
+            }
+            None => (0, 0, output_width, output_height),
+        };
+        let rect = sdl2::rect::Rect::new(x as i32, y as i32, width, height);
+        let pixels = self
+            .canvas
+            .read_pixels(rect, sdl2::pixels::PixelFormatEnum::RGBA32)
+            .map_err(|e| anyhow::anyhow!(e))?;
+        Ok(ImageData { width, height, pixels })
+    }
+
+    /// Show the frame, first saving it if a screenshot or sequence wants it
     pub fn present(&mut self) {
+        if let Some(path) = self.capture.next_frame_path() {
+            match self.read_frame() {
+                Ok(image) => self.capture_writer.write(path, image),
+                Err(e) => {
+                    log::warn!("Failed to read back frame: {:#}", e);
+                    self.capture.stop_sequence();
+                }
+            }
+        }
         self.canvas.present();
     }
 }
@@ -0,0 +1,101 @@
+use crate::engine::core::Transform;
+use crate::engine::rendering::{LayeredSprite, ParallaxConfig, RenderLayer, Renderer, Sprite};
+
+/// One sprite waiting to be drawn
+#[derive(Debug, Clone)]
+pub struct DrawCommand {
+    pub layer: RenderLayer,
+    /// Order within the layer, lowest first. Defaults to the sprite's bottom edge so lower things draw in front.
+    pub sort_key: f32,
+    pub sprite: Sprite,
+    pub transform: Transform,
+    /// Overrides the layer's default parallax
+    pub parallax: Option<ParallaxConfig>,
+    /// Skip the layer's atmosphere tint
+    pub untinted: bool,
+}
+
+impl DrawCommand {
+    pub fn new(layer: RenderLayer, sprite: Sprite, transform: Transform) -> Self {
+        let sort_key = transform.position.y + sprite.size.y * transform.scale.y.abs() / 2.0;
+        Self {
+            layer,
+            sort_key,
+            sprite,
+            transform,
+            parallax: None,
+            untinted: false,
+        }
+    }
+
+    pub fn with_sort_key(mut self, sort_key: f32) -> Self {
+        self.sort_key = sort_key;
+        self
+    }
+
+    pub fn with_parallax(mut self, parallax: ParallaxConfig) -> Self {
+        self.parallax = Some(parallax);
+        self
+    }
+
+    pub fn untinted(mut self) -> Self {
+        self.untinted = true;
+        self
+    }
+}
+
+/// Collects a frame's world sprites and draws them back to front in one pass
+#[derive(Debug, Default)]
+pub struct RenderQueue {
+    commands: Vec<DrawCommand>,
+    /// Seconds since the queue was created, for auto-scrolling layers
+    pub elapsed: f32,
+}
+
+impl RenderQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, command: DrawCommand) {
+        self.commands.push(command);
+    }
+
+    pub fn submit(&mut self, layer: RenderLayer, sprite: Sprite, transform: Transform) {
+        self.push(DrawCommand::new(layer, sprite, transform));
+    }
+
+    pub fn submit_layered(&mut self, layered: &LayeredSprite, transform: Transform) {
+        let command = DrawCommand::new(layered.layer, layered.sprite.clone(), transform);
+        self.push(command.with_parallax(layered.get_parallax()));
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    pub fn advance(&mut self, delta_time: f32) {
+        self.elapsed += delta_time;
+    }
+
+    /// Sort by layer then sort key, draw everything with parallax and atmosphere applied, and empty the queue.
+    /// Commands with equal layer and key keep their submission order.
+    pub fn flush(&mut self, renderer: &mut Renderer) {
+        self.commands
+            .sort_by(|a, b| a.layer.cmp(&b.layer).then(a.sort_key.total_cmp(&b.sort_key)));
+
+        let camera_offset = renderer.camera.position;
+        for mut command in self.commands.drain(..) {
+            let parallax = command.parallax.unwrap_or_else(|| command.layer.default_parallax());
+            command.transform.position = parallax.apply(command.transform.position, camera_offset, self.elapsed);
+            if !command.untinted {
+                command.sprite.color = command.layer.get_atmosphere_tint(command.sprite.color);
+            }
+            renderer.draw_sprite(&command.sprite, &command.transform);
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::engine::core::{Color, Rect};
+use crate::engine::rendering::TextureHandle;
 use glam::Vec2;
 
 #[derive(Debug, Clone)]
@@ -6,6 +7,12 @@ pub struct Sprite {
     pub size: Vec2,
     pub color: Color,
     pub source_rect: Option<Rect>,
+    /// Texture to blit `source_rect` from, tinted by `color`. Drawn as a plain colored rect when None.
+    pub texture: Option<TextureHandle>,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    /// Rotation pivot in sprite pixels from the top-left corner; None pivots on the center
+    pub origin: Option<Vec2>,
 }
 
 impl Sprite {
@@ -14,6 +21,10 @@ impl Sprite {
             size,
             color,
             source_rect: None,
+            texture: None,
+            flip_x: false,
+            flip_y: false,
+            origin: None,
         }
     }
 
@@ -21,6 +32,22 @@ impl Sprite {
         self.source_rect = Some(rect);
         self
     }
+
+    pub fn with_flip(mut self, flip_x: bool, flip_y: bool) -> Self {
+        self.flip_x = flip_x;
+        self.flip_y = flip_y;
+        self
+    }
+
+    pub fn with_origin(mut self, origin: Vec2) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    pub fn with_texture(mut self, texture: TextureHandle) -> Self {
+        self.texture = Some(texture);
+        self
+    }
 }
 
 impl Default for Sprite {
@@ -29,6 +56,10 @@ impl Default for Sprite {
             size: Vec2::new(32.0, 32.0),
             color: Color::WHITE,
             source_rect: None,
+            texture: None,
+            flip_x: false,
+            flip_y: false,
+            origin: None,
         }
     }
 }
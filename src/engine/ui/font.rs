@@ -264,6 +264,117 @@ impl BitmapFont {
             vec![false, false, true, false, false],
         ]);
         
+        // Remaining uppercase letters
+        char_data.insert('H', vec![
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![true, true, true, true, true],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+        ]);
+        
+        char_data.insert('J', vec![
+            vec![false, false, true, true, true],
+            vec![false, false, false, true, false],
+            vec![false, false, false, true, false],
+            vec![false, false, false, true, false],
+            vec![false, false, false, true, false],
+            vec![true, false, false, true, false],
+            vec![false, true, true, false, false],
+        ]);
+        
+        char_data.insert('K', vec![
+            vec![true, false, false, false, true],
+            vec![true, false, false, true, false],
+            vec![true, false, true, false, false],
+            vec![true, true, false, false, false],
+            vec![true, false, true, false, false],
+            vec![true, false, false, true, false],
+            vec![true, false, false, false, true],
+        ]);
+        
+        char_data.insert('O', vec![
+            vec![false, true, true, true, false],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![false, true, true, true, false],
+        ]);
+        
+        char_data.insert('Q', vec![
+            vec![false, true, true, true, false],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![true, false, true, false, true],
+            vec![true, false, false, true, false],
+            vec![false, true, true, false, true],
+        ]);
+        
+        char_data.insert('R', vec![
+            vec![true, true, true, true, false],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![true, true, true, true, false],
+            vec![true, false, true, false, false],
+            vec![true, false, false, true, false],
+            vec![true, false, false, false, true],
+        ]);
+        
+        char_data.insert('U', vec![
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![false, true, true, true, false],
+        ]);
+        
+        char_data.insert('W', vec![
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![true, false, true, false, true],
+            vec![true, false, true, false, true],
+            vec![true, true, false, true, true],
+            vec![true, false, false, false, true],
+        ]);
+        
+        char_data.insert('X', vec![
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![false, true, false, true, false],
+            vec![false, false, true, false, false],
+            vec![false, true, false, true, false],
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+        ]);
+        
+        char_data.insert('Y', vec![
+            vec![true, false, false, false, true],
+            vec![true, false, false, false, true],
+            vec![false, true, false, true, false],
+            vec![false, false, true, false, false],
+            vec![false, false, true, false, false],
+            vec![false, false, true, false, false],
+            vec![false, false, true, false, false],
+        ]);
+        
+        char_data.insert('Z', vec![
+            vec![true, true, true, true, true],
+            vec![false, false, false, false, true],
+            vec![false, false, false, true, false],
+            vec![false, false, true, false, false],
+            vec![false, true, false, false, false],
+            vec![true, false, false, false, false],
+            vec![true, true, true, true, true],
+        ]);
+        
         // Lowercase letters (subset)
         char_data.insert('a', vec![
             vec![false, false, false, false, false],
@@ -476,6 +587,76 @@ impl BitmapFont {
             vec![false, false, false, false, false],
         ]);
         
+        char_data.insert('<', vec![
+            vec![false, false, false, true, false],
+            vec![false, false, true, false, false],
+            vec![false, true, false, false, false],
+            vec![true, false, false, false, false],
+            vec![false, true, false, false, false],
+            vec![false, false, true, false, false],
+            vec![false, false, false, true, false],
+        ]);
+        
+        char_data.insert('>', vec![
+            vec![false, true, false, false, false],
+            vec![false, false, true, false, false],
+            vec![false, false, false, true, false],
+            vec![false, false, false, false, true],
+            vec![false, false, false, true, false],
+            vec![false, false, true, false, false],
+            vec![false, true, false, false, false],
+        ]);
+        
+        char_data.insert('+', vec![
+            vec![false, false, false, false, false],
+            vec![false, false, true, false, false],
+            vec![false, false, true, false, false],
+            vec![true, true, true, true, true],
+            vec![false, false, true, false, false],
+            vec![false, false, true, false, false],
+            vec![false, false, false, false, false],
+        ]);
+        
+        char_data.insert('%', vec![
+            vec![true, true, false, false, false],
+            vec![true, true, false, false, true],
+            vec![false, false, false, true, false],
+            vec![false, false, true, false, false],
+            vec![false, true, false, false, false],
+            vec![true, false, false, true, true],
+            vec![false, false, false, true, true],
+        ]);
+        
+        char_data.insert('!', vec![
+            vec![false, false, true, false, false],
+            vec![false, false, true, false, false],
+            vec![false, false, true, false, false],
+            vec![false, false, true, false, false],
+            vec![false, false, true, false, false],
+            vec![false, false, false, false, false],
+            vec![false, false, true, false, false],
+        ]);
+        
+        char_data.insert('[', vec![
+            vec![false, true, true, true, false],
+            vec![false, true, false, false, false],
+            vec![false, true, false, false, false],
+            vec![false, true, false, false, false],
+            vec![false, true, false, false, false],
+            vec![false, true, false, false, false],
+            vec![false, true, true, true, false],
+        ]);
+        
+        char_data.insert(']', vec![
+            vec![false, true, true, true, false],
+            vec![false, false, false, true, false],
+            vec![false, false, false, true, false],
+            vec![false, false, false, true, false],
+            vec![false, false, false, true, false],
+            vec![false, false, false, true, false],
+            vec![false, true, true, true, false],
+        ]);
+        
         char_data.insert(' ', vec![
             vec![false, false, false, false, false],
             vec![false, false, false, false, false],
@@ -1,7 +1,27 @@
 use crate::engine::core::Color;
 use crate::engine::rendering::Renderer;
+use crate::engine::ui::Anchor;
 use glam::Vec2;
 
+/// An allied unit on the minimap, with the point its current order refers to
+#[derive(Debug, Clone)]
+pub struct CompanionMarker {
+    pub position: Vec2,
+    pub anchor: Option<Vec2>,
+    pub color: Color,
+    pub label: &'static str,
+}
+
+/// Everything plotted on the minimap around the player
+#[derive(Debug, Clone, Copy)]
+pub struct MinimapMarkers<'a> {
+    /// Position and whether it's a boss
+    pub enemies: &'a [(Vec2, bool)],
+    pub collectibles: &'a [Vec2],
+    pub goal: Option<Vec2>,
+    pub companions: &'a [CompanionMarker],
+}
+
 #[derive(Debug, Clone)]
 pub struct Minimap {
     pub position: Vec2,      // Screen position (top-right corner usually)
@@ -26,15 +46,12 @@ impl Minimap {
         }
     }
     
-    pub fn render(
-        &self,
-        renderer: &mut Renderer,
-        player_pos: Vec2,
-        enemies: &[(Vec2, bool)],  // Position and whether it's a boss
-        collectibles: &[Vec2],
-        goal_pos: Option<Vec2>,
-        camera_offset: Vec2,
-    ) {
+    /// Place the minimap against a corner of the screen
+    pub fn anchor_to(&mut self, anchor: Anchor, screen_size: Vec2, offset: Vec2) {
+        self.position = anchor.calculate_position(screen_size, self.size, offset);
+    }
+
+    pub fn render(&self, renderer: &mut Renderer, player_pos: Vec2, markers: &MinimapMarkers, camera_offset: Vec2) {
         // Calculate actual screen position (relative to camera)
         let screen_pos = self.position - camera_offset;
         
@@ -48,7 +65,7 @@ impl Minimap {
         let minimap_center = screen_pos + self.size / 2.0;
         
         // Draw enemies as red dots
-        for (enemy_pos, is_boss) in enemies {
+        for (enemy_pos, is_boss) in markers.enemies {
             let relative_pos = (*enemy_pos - player_pos) * self.world_scale;
             
             // Check if enemy is within minimap bounds
@@ -71,7 +88,7 @@ impl Minimap {
         }
         
         // Draw collectibles as blue dots
-        for collectible_pos in collectibles {
+        for collectible_pos in markers.collectibles {
             let relative_pos = (*collectible_pos - player_pos) * self.world_scale;
             
             if relative_pos.x.abs() < self.size.x / 2.0 && relative_pos.y.abs() < self.size.y / 2.0 {
@@ -86,7 +103,7 @@ impl Minimap {
         }
         
         // Draw goal as green marker
-        if let Some(goal) = goal_pos {
+        if let Some(goal) = markers.goal {
             let relative_pos = (goal - player_pos) * self.world_scale;
             
             // Clamp to edge if outside minimap
@@ -104,6 +121,28 @@ impl Minimap {
             );
         }
         
+        // Draw companion mechs, their order target and the order name below the map
+        for (index, companion) in markers.companions.iter().enumerate() {
+            let color = Color::new(companion.color.r, companion.color.g, companion.color.b, self.opacity);
+            let mech_pos = minimap_center + self.clamp_to_map((companion.position - player_pos) * self.world_scale);
+
+            if let Some(anchor) = companion.anchor {
+                let anchor_pos = minimap_center + self.clamp_to_map((anchor - player_pos) * self.world_scale);
+
+                // Dotted line from the mech to where it was sent
+                for step in 1..4 {
+                    let dot = mech_pos + (anchor_pos - mech_pos) * (step as f32 / 4.0);
+                    renderer.draw_ui_rect(dot - Vec2::splat(1.0), Vec2::splat(2.0), color);
+                }
+                renderer.draw_ui_rect_outline(anchor_pos - Vec2::splat(4.0), Vec2::splat(8.0), color, 1.0);
+            }
+
+            renderer.draw_ui_rect(mech_pos - Vec2::splat(3.5), Vec2::splat(7.0), color);
+
+            let label_pos = screen_pos + Vec2::new(0.0, self.size.y + 6.0 + index as f32 * 14.0);
+            renderer.draw_ui_text(label_pos, &format!("MECH: {}", companion.label), color, 10);
+        }
+
         // Draw player at center (white dot) using UI rect
         renderer.draw_ui_rect(
             minimap_center - Vec2::splat(2.5),
@@ -120,6 +159,14 @@ impl Minimap {
         );
     }
     
+    /// Keep a minimap-relative offset inside the map so off-screen markers stick to the edge
+    fn clamp_to_map(&self, relative_pos: Vec2) -> Vec2 {
+        Vec2::new(
+            relative_pos.x.clamp(-self.size.x / 2.0 + 4.0, self.size.x / 2.0 - 4.0),
+            relative_pos.y.clamp(-self.size.y / 2.0 + 4.0, self.size.y / 2.0 - 4.0),
+        )
+    }
+
     fn draw_border(&self, renderer: &mut Renderer, screen_pos: Vec2) {
         // Use draw_ui_rect_outline for the border
         renderer.draw_ui_rect_outline(
@@ -1,5 +1,7 @@
 use crate::engine::core::{Color, Transform};
-use crate::engine::rendering::mock_assets::{BuildingAsset, BuildingStyle};
+use crate::engine::rendering::mock_assets::{BuildingAsset, BuildingStyle, StreetProp, StreetPropType};
+use crate::engine::rendering::{Camera, DrawCommand, LightMap, ParallaxConfig, PointLight, RenderLayer, RenderQueue, Sprite};
+use crate::game::{DayNightCycle, Level};
 use glam::Vec2;
 
 /// Component representing a building in the game world
@@ -119,6 +121,165 @@ impl BuildingGenerator {
         
         skyline
     }
+
+    /// Lamp posts, bins and signs along the street, left to right
+    pub fn generate_street_props(&self, width: f32, ground_y: f32) -> Vec<StreetProp> {
+        use crate::engine::rendering::mock_assets::MockAssetGenerator;
+        use rand::{Rng, SeedableRng};
+        use rand::rngs::StdRng;
+
+        let mut props = Vec::new();
+        let mut rng = StdRng::seed_from_u64(self.seed + 100);
+        let mut asset_gen = MockAssetGenerator::new(self.seed + 100);
+
+        let mut x = rng.gen_range(100.0..400.0);
+        while x < width {
+            let prop_type = match rng.gen_range(0..10) {
+                0..=4 => StreetPropType::LampPost,
+                5..=7 => StreetPropType::TrashCan,
+                _ => StreetPropType::Sign,
+            };
+            props.push(asset_gen.generate_street_prop(x, ground_y, prop_type));
+            x += rng.gen_range(300.0..900.0);
+        }
+
+        props
+    }
+}
+
+/// Queue the visible part of the level's city: far and mid skylines, the street-level block and street props.
+/// Everything is sorted left to right, so only the slice inside the view is walked.
+pub fn submit_city(queue: &mut RenderQueue, level: &Level, camera: &Camera, day_night: &DayNightCycle) {
+    let half_view = camera.viewport_size.x / camera.zoom / 2.0;
+    let lit_fraction = day_night.get_window_light_fraction();
+
+    let skyline_layers = [RenderLayer::FarBackground, RenderLayer::MidBackground];
+    for (skyline, layer) in level.background_buildings.iter().zip(skyline_layers) {
+        let parallax = city_parallax(layer);
+        let (start, end) = visible_range(camera.position.x * parallax.scroll_factor_x, half_view);
+        for asset in visible(skyline, |asset| (asset.position.x, asset.size.x), start, end) {
+            submit_building(queue, asset, layer, parallax, lit_fraction);
+        }
+    }
+
+    let parallax = city_parallax(RenderLayer::NearBackground);
+    let (start, end) = visible_range(camera.position.x * parallax.scroll_factor_x, half_view);
+    for building in visible(&level.buildings, |b| (b.asset.position.x, b.asset.size.x), start, end) {
+        submit_building(queue, &building.asset, RenderLayer::NearBackground, parallax, lit_fraction);
+    }
+
+    // Props stand on the street itself, so they scroll with the world
+    let street = ParallaxConfig::new(1.0, 1.0);
+    let (start, end) = visible_range(camera.position.x, half_view);
+    for prop in visible(&level.street_props, |p| (p.position.x - 40.0, p.size.x + 80.0), start, end) {
+        let bottom = prop.position.y + prop.size.y;
+        let body = Sprite::new(prop.size, prop.color);
+        queue.push(
+            DrawCommand::new(RenderLayer::NearBackground, body, Transform::new(prop.position + prop.size / 2.0))
+                .with_sort_key(bottom)
+                .with_parallax(street),
+        );
+        for &(offset, size, color) in &prop.details {
+            let detail = Sprite::new(size, color);
+            queue.push(
+                DrawCommand::new(RenderLayer::NearBackground, detail, Transform::new(prop.position + offset + size / 2.0))
+                    .with_sort_key(bottom)
+                    .with_parallax(street),
+            );
+        }
+    }
+}
+
+/// Lights for the lit windows of the nearest buildings and the lamp posts along the street.
+/// Backdrop lights sit behind the level geometry, so they never cast shadows.
+pub fn submit_city_lights(lights: &mut LightMap, level: &Level, camera: &Camera, day_night: &DayNightCycle) {
+    let half_view = camera.viewport_size.x / camera.zoom / 2.0;
+    let lit_fraction = day_night.get_window_light_fraction();
+
+    let parallax = city_parallax(RenderLayer::NearBackground);
+    let (start, end) = visible_range(camera.position.x * parallax.scroll_factor_x, half_view);
+    for building in visible(&level.buildings, |b| (b.asset.position.x, b.asset.size.x), start, end) {
+        let asset = &building.asset;
+        let building_id = asset.position.x.to_bits();
+        for (index, window) in asset.windows.iter().enumerate() {
+            if window_hash(building_id, index as u32) >= lit_fraction {
+                continue;
+            }
+            let center = asset.position + Vec2::new(window.x + window.width / 2.0, window.y + window.height / 2.0);
+            let light = PointLight::new(window.width * 2.5, Color::new(255, 220, 150, 255))
+                .with_intensity(0.5)
+                .without_shadows();
+            lights.add(parallax.apply(center, camera.position, 0.0), light);
+        }
+    }
+
+    let (start, end) = visible_range(camera.position.x, half_view);
+    for prop in visible(&level.street_props, |p| (p.position.x - 40.0, p.size.x + 80.0), start, end) {
+        if let StreetPropType::LampPost = prop.prop_type {
+            for &(offset, size, _) in &prop.details {
+                let light = PointLight::new(180.0, Color::new(255, 230, 170, 255)).without_shadows();
+                lights.add(prop.position + offset + size / 2.0, light);
+            }
+        }
+    }
+}
+
+/// Backdrops scroll sideways at their layer's rate but stay planted on the horizon vertically
+fn city_parallax(layer: RenderLayer) -> ParallaxConfig {
+    ParallaxConfig::new(layer.default_parallax().scroll_factor_x, 1.0)
+}
+
+/// World x range a layer shows when its scrolled camera centre is at `center`
+fn visible_range(center: f32, half_view: f32) -> (f32, f32) {
+    (center - half_view, center + half_view)
+}
+
+/// Items overlapping [start, end], given items sorted by their left edge
+fn visible<T>(items: &[T], extent: impl Fn(&T) -> (f32, f32) + Copy, start: f32, end: f32) -> impl Iterator<Item = &T> {
+    // Nothing is wider than MAX_CITY_ITEM_WIDTH, so anything starting further left than that can't reach the view
+    let first = items.partition_point(|item| extent(item).0 < start - MAX_CITY_ITEM_WIDTH);
+    items[first..]
+        .iter()
+        .take_while(move |item| extent(item).0 <= end)
+        .filter(move |item| {
+            let (x, width) = extent(item);
+            x + width >= start
+        })
+}
+
+/// Widest building the generators make, used to bound the visibility search
+const MAX_CITY_ITEM_WIDTH: f32 = 600.0;
+
+fn submit_building(queue: &mut RenderQueue, asset: &BuildingAsset, layer: RenderLayer, parallax: ParallaxConfig, lit_fraction: f32) {
+    // Windows share the building's key so they land on top of it in submission order
+    let bottom = asset.position.y + asset.size.y;
+    let body = Sprite::new(asset.size, asset.base_color);
+    queue.push(
+        DrawCommand::new(layer, body, Transform::new(asset.position + asset.size / 2.0))
+            .with_sort_key(bottom)
+            .with_parallax(parallax),
+    );
+
+    let building_id = asset.position.x.to_bits();
+    for (index, window) in asset.windows.iter().enumerate() {
+        let lit = window_hash(building_id, index as u32) < lit_fraction;
+        let size = Vec2::new(window.width, window.height);
+        let center = asset.position + Vec2::new(window.x, window.y) + size / 2.0;
+        queue.push(
+            DrawCommand::new(layer, Sprite::new(size, asset.get_window_color(lit)), Transform::new(center))
+                .with_sort_key(bottom)
+                .with_parallax(parallax),
+        );
+    }
+}
+
+/// Stable pseudo-random value in [0, 1) per window, so the same windows light up every night
+fn window_hash(building: u32, window: u32) -> f32 {
+    let mut h = building.wrapping_mul(0x9E37_79B9) ^ window.wrapping_mul(0x85EB_CA6B);
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x7FEB_352D);
+    h ^= h >> 15;
+    (h >> 8) as f32 / (1u32 << 24) as f32
 }
 
 /// Component for building health and destruction
@@ -309,4 +309,29 @@ impl DayNightCycle {
             }
         }
     }
-}
\ No newline at end of file
+
+    /// Share of building windows with the lights on: a few by day, most of them at night
+    pub fn get_window_light_fraction(&self) -> f32 {
+        let phase = self.get_phase_progress();
+
+        if self.is_day {
+            if phase < 0.25 {
+                0.3 // Dawn
+            } else if phase < 0.75 {
+                0.05
+            } else {
+                0.4 // Sunset
+            }
+        } else if phase < 0.5 {
+            0.7 // Evening
+        } else {
+            0.85
+        }
+    }
+}
+
+impl Default for DayNightCycle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
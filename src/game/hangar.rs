@@ -0,0 +1,198 @@
+use crate::engine::core::Color;
+use crate::engine::platform::InputState;
+use crate::engine::rendering::Renderer;
+use crate::engine::ui::Anchor;
+use crate::systems::mech::{chassis_registry, MechLoadout, MechModule, MechWeaponType};
+use glam::Vec2;
+use sdl2::keyboard::Keycode;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HangarAction {
+    None,
+    /// Player confirmed the loadout and wants to deploy
+    Launch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HangarSlot {
+    Chassis,
+    Primary,
+    Secondary,
+    Module(usize),
+}
+
+/// Between-level screen for assigning mech weapons and modules
+pub struct HangarScreen {
+    pub is_open: bool,
+    pub loadout: MechLoadout,
+    selected_row: usize,
+    screen_size: Vec2,
+}
+
+impl HangarScreen {
+    pub fn new(screen_width: f32, screen_height: f32, loadout: MechLoadout) -> Self {
+        Self {
+            is_open: false,
+            loadout,
+            selected_row: 0,
+            screen_size: Vec2::new(screen_width, screen_height),
+        }
+    }
+
+    pub fn resize(&mut self, screen_size: Vec2) {
+        self.screen_size = screen_size;
+    }
+
+    pub fn open(&mut self) {
+        self.is_open = true;
+        self.selected_row = 0;
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+    }
+
+    fn slots(&self) -> Vec<HangarSlot> {
+        let mut slots = vec![HangarSlot::Chassis, HangarSlot::Primary, HangarSlot::Secondary];
+        slots.extend((0..self.loadout.modules.len()).map(HangarSlot::Module));
+        slots
+    }
+
+    pub fn handle_input(&mut self, input: &InputState) -> HangarAction {
+        let row_count = self.slots().len();
+
+        if input.is_key_pressed(Keycode::Up) || input.is_key_pressed(Keycode::W) {
+            self.selected_row = (self.selected_row + row_count - 1) % row_count;
+        }
+        if input.is_key_pressed(Keycode::Down) || input.is_key_pressed(Keycode::S) {
+            self.selected_row = (self.selected_row + 1) % row_count;
+        }
+        if input.is_key_pressed(Keycode::Left) || input.is_key_pressed(Keycode::A) {
+            self.cycle_selected(-1);
+        }
+        if input.is_key_pressed(Keycode::Right) || input.is_key_pressed(Keycode::D) {
+            self.cycle_selected(1);
+        }
+
+        if input.is_key_pressed(Keycode::Return) || input.is_key_pressed(Keycode::Space) {
+            return HangarAction::Launch;
+        }
+
+        HangarAction::None
+    }
+
+    fn cycle_selected(&mut self, direction: i32) {
+        match self.slots()[self.selected_row] {
+            HangarSlot::Chassis => {
+                let all = chassis_registry().all();
+                let index = all.iter().position(|c| c.id == self.loadout.chassis).unwrap_or(0);
+                let next = (index as i32 + direction).rem_euclid(all.len() as i32) as usize;
+                self.loadout.set_chassis(&all[next].id);
+            }
+            HangarSlot::Primary => {
+                self.loadout.primary = cycle_weapon(self.loadout.primary, direction);
+            }
+            HangarSlot::Secondary => {
+                self.loadout.secondary = cycle_weapon(self.loadout.secondary, direction);
+            }
+            HangarSlot::Module(index) => {
+                // Options are "empty" followed by every module type
+                let options: Vec<Option<MechModule>> = std::iter::once(None)
+                    .chain(MechModule::ALL.iter().copied().map(Some))
+                    .collect();
+                let current = options
+                    .iter()
+                    .position(|m| *m == self.loadout.modules[index])
+                    .unwrap_or(0);
+                let next = (current as i32 + direction).rem_euclid(options.len() as i32) as usize;
+                self.loadout.modules[index] = options[next];
+            }
+        }
+    }
+
+    fn slot_label(&self, slot: HangarSlot) -> (String, String) {
+        match slot {
+            HangarSlot::Chassis => ("CHASSIS".to_string(), self.loadout.chassis_def().name.clone()),
+            HangarSlot::Primary => ("PRIMARY".to_string(), self.loadout.primary.name().to_string()),
+            HangarSlot::Secondary => ("SECONDARY".to_string(), self.loadout.secondary.name().to_string()),
+            HangarSlot::Module(index) => (
+                format!("MODULE {}", index + 1),
+                self.loadout.modules[index]
+                    .map(|m| m.name())
+                    .unwrap_or("EMPTY")
+                    .to_string(),
+            ),
+        }
+    }
+
+    pub fn render(&self, renderer: &mut Renderer) {
+        if !self.is_open {
+            return;
+        }
+
+        // Full-screen backdrop
+        renderer.draw_ui_rect(Vec2::ZERO, self.screen_size, Color::new(10, 12, 20, 255));
+
+        let panel_size = Vec2::new(640.0, 460.0);
+        let panel_pos = Anchor::Center.calculate_position(self.screen_size, panel_size, Vec2::ZERO);
+        renderer.draw_ui_rect(panel_pos, panel_size, Color::new(20, 20, 30, 230));
+        renderer.draw_ui_rect_outline(panel_pos, panel_size, Color::new(100, 150, 200, 255), 2.0);
+
+        renderer.draw_ui_text(
+            panel_pos + Vec2::new(20.0, 20.0),
+            "HANGAR - MECH LOADOUT",
+            Color::new(100, 200, 255, 255),
+            24,
+        );
+
+        // Slot rows
+        for (row, slot) in self.slots().into_iter().enumerate() {
+            let row_pos = panel_pos + Vec2::new(20.0, 80.0 + row as f32 * 44.0);
+            let selected = row == self.selected_row;
+
+            if selected {
+                renderer.draw_ui_rect(row_pos - Vec2::new(8.0, 8.0), Vec2::new(380.0, 34.0), Color::new(50, 60, 100, 200));
+                renderer.draw_ui_rect_outline(row_pos - Vec2::new(8.0, 8.0), Vec2::new(380.0, 34.0), Color::new(100, 150, 255, 255), 1.0);
+            }
+
+            let (label, value) = self.slot_label(slot);
+            let value_text = if selected { format!("< {} >", value) } else { value };
+            renderer.draw_ui_text(row_pos, &label, Color::new(160, 160, 180, 255), 16);
+            renderer.draw_ui_text(row_pos + Vec2::new(130.0, 0.0), &value_text, Color::WHITE, 16);
+        }
+
+        // Stat preview for the configured mech
+        let mech = self.loadout.build_mech();
+        let stats_pos = panel_pos + Vec2::new(420.0, 80.0);
+        let stats = [
+            format!("MASS   {:.0}", mech.mass),
+            format!("HEALTH {:.0}", mech.max_health),
+            format!("ENERGY {:.0}", mech.max_energy),
+            format!("SPEED  {:.0}", mech.normal_speed),
+            format!("BOOST  {:.0}", mech.boost_speed),
+            format!("JUMP   {:.0}", mech.jump_power),
+        ];
+        for (i, line) in stats.iter().enumerate() {
+            renderer.draw_ui_text(
+                stats_pos + Vec2::new(0.0, i as f32 * 28.0),
+                line,
+                Color::new(100, 255, 100, 255),
+                16,
+            );
+        }
+
+        renderer.draw_ui_text(
+            panel_pos + Vec2::new(20.0, panel_size.y - 40.0),
+            "UP/DOWN SELECT   LEFT/RIGHT CHANGE   ENTER LAUNCH",
+            Color::new(200, 200, 200, 255),
+            14,
+        );
+    }
+}
+
+fn cycle_weapon(current: MechWeaponType, direction: i32) -> MechWeaponType {
+    let all = MechWeaponType::ALL;
+    let index = all.iter().position(|w| *w == current).unwrap_or(0);
+    let next = (index as i32 + direction).rem_euclid(all.len() as i32) as usize;
+    all[next]
+}
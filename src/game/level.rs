@@ -1,10 +1,18 @@
 use crate::engine::core::{Color, Rect};
 use crate::engine::rendering::mock_assets::{BuildingAsset, MockAssetGenerator, StreetProp};
-use crate::game::buildings::Building;
+use crate::engine::rendering::RenderLayer;
+use crate::game::buildings::{Building, BuildingGenerator};
+use crate::game::trigger::{TriggerAction, TriggerCondition, TriggerEvent, TriggerSubject, TriggerVolume};
 use crate::game::win_condition::WinCondition;
 use glam::Vec2;
 
 pub const TILE_SIZE: f32 = 32.0;
+/// How far past its parallax span each city backdrop is generated, enough for the widest zoomed-out view
+const CITY_VIEW_MARGIN: f32 = 6000.0;
+/// How far above the top row the camera may look into open sky
+const CAMERA_HEADROOM: f32 = 2000.0;
+/// How far into a one-way platform something can already be and still land on it
+const ONE_WAY_TOLERANCE: f32 = 4.0;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TileType {
@@ -13,12 +21,70 @@ pub enum TileType {
     Platform,
     Wall,
     Destructible,
+    /// 45 degree ramp rising to the right
+    SlopeRight,
+    /// 45 degree ramp rising to the left
+    SlopeLeft,
+    /// Lower and upper halves of a 22.5 degree ramp rising to the right
+    GentleSlopeRightLow,
+    GentleSlopeRightHigh,
+    /// Lower and upper halves of a 22.5 degree ramp rising to the left
+    GentleSlopeLeftLow,
+    GentleSlopeLeftHigh,
+    /// Solid bottom half of a tile
+    HalfBlock,
+}
+
+/// Collision shape of a solid tile
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TileShape {
+    Full,
+    /// Solid below a straight surface, given as heights above the tile bottom at its left and right edges
+    Surface { left: f32, right: f32 },
+}
+
+impl TileShape {
+    /// Height of the solid part above the tile bottom, `local_x` pixels in from the left edge
+    pub fn height_at(&self, local_x: f32) -> f32 {
+        match *self {
+            TileShape::Full => TILE_SIZE,
+            TileShape::Surface { left, right } => {
+                let t = (local_x / TILE_SIZE).clamp(0.0, 1.0);
+                left + (right - left) * t
+            }
+        }
+    }
+
+    /// Sloped surfaces can be walked up without jumping
+    pub fn is_ramp(&self) -> bool {
+        matches!(*self, TileShape::Surface { left, right } if left != right)
+    }
+
+    /// Whether a rect overlapping the tile's square at `tile_rect` actually touches the solid part
+    pub fn intersects(&self, tile_rect: &Rect, rect: &Rect) -> bool {
+        if !rect.intersects(tile_rect) {
+            return false;
+        }
+        match self {
+            TileShape::Full => true,
+            TileShape::Surface { .. } => {
+                // The surface is straight, so its highest point under the rect is at one of the ends
+                let from = rect.x - tile_rect.x;
+                let to = rect.x + rect.width - tile_rect.x;
+                let highest = self.height_at(from).max(self.height_at(to));
+                rect.y + rect.height > tile_rect.y + TILE_SIZE - highest
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Tile {
     pub tile_type: TileType,
     pub solid: bool,
+    pub shape: TileShape,
+    /// Can be stood on from above but passed through from below and the sides
+    pub one_way: bool,
     pub color: Color,
 }
 
@@ -27,19 +93,58 @@ impl Tile {
         let (solid, color) = match tile_type {
             TileType::Empty => (false, Color::new(0, 0, 0, 0)),
             TileType::Ground => (true, Color::new(80, 60, 40, 255)),
-            TileType::Platform => (true, Color::new(120, 100, 80, 255)),
+            TileType::Platform => (false, Color::new(120, 100, 80, 255)),
             TileType::Wall => (true, Color::new(100, 100, 100, 255)),
             TileType::Destructible => (true, Color::new(160, 140, 120, 255)),
+            TileType::SlopeRight
+            | TileType::SlopeLeft
+            | TileType::GentleSlopeRightLow
+            | TileType::GentleSlopeRightHigh
+            | TileType::GentleSlopeLeftLow
+            | TileType::GentleSlopeLeftHigh => (true, Color::new(80, 60, 40, 255)),
+            TileType::HalfBlock => (true, Color::new(110, 105, 95, 255)),
+        };
+
+        let half = TILE_SIZE / 2.0;
+        let shape = match tile_type {
+            TileType::SlopeRight => TileShape::Surface { left: 0.0, right: TILE_SIZE },
+            TileType::SlopeLeft => TileShape::Surface { left: TILE_SIZE, right: 0.0 },
+            TileType::GentleSlopeRightLow => TileShape::Surface { left: 0.0, right: half },
+            TileType::GentleSlopeRightHigh => TileShape::Surface { left: half, right: TILE_SIZE },
+            TileType::GentleSlopeLeftHigh => TileShape::Surface { left: TILE_SIZE, right: half },
+            TileType::GentleSlopeLeftLow => TileShape::Surface { left: half, right: 0.0 },
+            TileType::HalfBlock => TileShape::Surface { left: half, right: half },
+            _ => TileShape::Full,
         };
 
         Self {
             tile_type,
             solid,
+            shape,
+            one_way: tile_type == TileType::Platform,
             color,
         }
     }
 }
 
+/// How much objective progress survives a death, measured from the last checkpoint
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RespawnRules {
+    /// Collectibles picked up since the checkpoint stay collected
+    pub keep_items: bool,
+    /// The level clock keeps running instead of rolling back to the checkpoint
+    pub keep_time: bool,
+}
+
+impl Default for RespawnRules {
+    fn default() -> Self {
+        Self {
+            keep_items: true,
+            keep_time: true,
+        }
+    }
+}
+
 pub struct Level {
     pub tiles: Vec<Vec<Tile>>,
     pub width: usize,
@@ -59,11 +164,20 @@ pub struct Level {
     pub time_limit: Option<f32>,
     pub collectibles: Vec<(Vec2, bool)>,
     pub boss_spawn: Option<Vec2>,
+    pub triggers: Vec<TriggerVolume>,
+    /// Checkpoint flag positions, in the order they're met
+    pub checkpoints: Vec<Vec2>,
+    pub respawn_rules: RespawnRules,
+    /// Tiles changed by `set_tile` since the renderer last looked
+    edited_tiles: Vec<(usize, usize)>,
+    /// Tile types as built, put back by `reset_tiles`
+    initial_tiles: Vec<Vec<TileType>>,
 }
 
 impl Level {
     pub fn new(width: usize, height: usize) -> Self {
         let tiles = vec![vec![Tile::new(TileType::Empty); width]; height];
+        let initial_tiles = tile_types(&tiles);
         let mut asset_gen = MockAssetGenerator::new(42);
         
         Self {
@@ -83,6 +197,11 @@ impl Level {
             time_limit: None,
             collectibles: Vec::new(),
             boss_spawn: None,
+            triggers: Vec::new(),
+            checkpoints: Vec::new(),
+            respawn_rules: RespawnRules::default(),
+            edited_tiles: Vec::new(),
+            initial_tiles,
         }
     }
 
@@ -102,6 +221,13 @@ impl Level {
                     '=' => TileType::Platform,
                     '|' => TileType::Wall,
                     'D' => TileType::Destructible,
+                    '/' => TileType::SlopeRight,
+                    '\\' => TileType::SlopeLeft,
+                    'r' => TileType::GentleSlopeRightLow,
+                    'R' => TileType::GentleSlopeRightHigh,
+                    'L' => TileType::GentleSlopeLeftHigh,
+                    'l' => TileType::GentleSlopeLeftLow,
+                    '_' => TileType::HalfBlock,
                     'B' => {
                         building_positions.push((x, y));
                         TileType::Empty
@@ -116,6 +242,8 @@ impl Level {
             }
         }
 
+        let initial_tiles = tile_types(&tiles);
+
         // Generate procedural assets (sky and clouds only for now)
         let mut asset_gen = MockAssetGenerator::new(42);
         
@@ -153,9 +281,21 @@ impl Level {
             time_limit: None,
             collectibles: Vec::new(),
             boss_spawn: None,
+            triggers: Vec::new(),
+            checkpoints: Vec::new(),
+            respawn_rules: RespawnRules::default(),
+            edited_tiles: Vec::new(),
+            initial_tiles,
         }
     }
 
+    /// World area the camera may show: the whole map plus open sky above it
+    pub fn camera_bounds(&self) -> Rect {
+        let width = self.width as f32 * TILE_SIZE;
+        let height = self.height as f32 * TILE_SIZE;
+        Rect::new(0.0, -CAMERA_HEADROOM, width, height + CAMERA_HEADROOM)
+    }
+
     pub fn get_tile(&self, x: usize, y: usize) -> Option<&Tile> {
         if x < self.width && y < self.height {
             Some(&self.tiles[y][x])
@@ -170,6 +310,52 @@ impl Level {
         self.get_tile(x, y)
     }
 
+    pub fn set_tile(&mut self, x: usize, y: usize, tile_type: TileType) {
+        if x < self.width && y < self.height {
+            self.tiles[y][x] = Tile::new(tile_type);
+            self.edited_tiles.push((x, y));
+        }
+    }
+
+    /// Undo every `set_tile` edit (closed gates, crushed rubble) for a fresh attempt at the level.
+    /// Restored tiles are reported by `take_edited_tiles` like any other edit.
+    pub fn reset_tiles(&mut self) {
+        let mut changed = Vec::new();
+        for (y, (row, initial_row)) in self.tiles.iter().zip(&self.initial_tiles).enumerate() {
+            for (x, (tile, &initial)) in row.iter().zip(initial_row).enumerate() {
+                if tile.tile_type != initial {
+                    changed.push((x, y, initial));
+                }
+            }
+        }
+        for (x, y, tile_type) in changed {
+            self.set_tile(x, y, tile_type);
+        }
+    }
+
+    /// Grid coordinates of tiles changed since the last call, so cached tile graphics can be redrawn
+    pub fn take_edited_tiles(&mut self) -> Vec<(usize, usize)> {
+        std::mem::take(&mut self.edited_tiles)
+    }
+
+    /// Grid coordinates of every tile of the given type overlapping a rect
+    pub fn tiles_of_type_in_rect(&self, rect: Rect, tile_type: TileType) -> Vec<(usize, usize)> {
+        let start_x = (rect.x / TILE_SIZE).floor().max(0.0) as usize;
+        let end_x = (((rect.x + rect.width) / TILE_SIZE).ceil().max(0.0) as usize).min(self.width);
+        let start_y = (rect.y / TILE_SIZE).floor().max(0.0) as usize;
+        let end_y = (((rect.y + rect.height) / TILE_SIZE).ceil().max(0.0) as usize).min(self.height);
+
+        let mut found = Vec::new();
+        for y in start_y..end_y {
+            for x in start_x..end_x {
+                if self.tiles[y][x].tile_type == tile_type {
+                    found.push((x, y));
+                }
+            }
+        }
+        found
+    }
+
     pub fn check_collision(&self, rect: Rect) -> bool {
         let start_x = ((rect.x / TILE_SIZE).floor() as usize).max(0);
         let end_x = (((rect.x + rect.width) / TILE_SIZE).ceil() as usize).min(self.width);
@@ -186,7 +372,7 @@ impl Level {
                             TILE_SIZE,
                             TILE_SIZE,
                         );
-                        if rect.intersects(&tile_rect) {
+                        if tile.shape.intersects(&tile_rect, &rect) {
                             return true;
                         }
                     }
@@ -196,6 +382,88 @@ impl Level {
         false
     }
 
+    /// No solid tile lies between two points. The tiles the points are in don't count,
+    /// so walls and floors still see what's in front of them.
+    pub fn line_of_sight(&self, from: Vec2, to: Vec2) -> bool {
+        let tile_of = |point: Vec2| ((point.x / TILE_SIZE).floor() as i64, (point.y / TILE_SIZE).floor() as i64);
+        let (start, end) = (tile_of(from), tile_of(to));
+
+        // Half-tile steps can't skip over a whole tile
+        let steps = (from.distance(to) / (TILE_SIZE / 2.0)).ceil() as usize;
+        for step in 1..steps {
+            let (x, y) = tile_of(from.lerp(to, step as f32 / steps as f32));
+            if (x, y) == start || (x, y) == end || x < 0 || y < 0 {
+                continue;
+            }
+            if self.get_tile(x as usize, y as usize).is_some_and(|tile| tile.solid) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Top edge of the first one-way platform under a rect whose bottom edge moves
+    /// from `from_bottom` down to `to_bottom`. Platforms only catch things coming from above.
+    pub fn one_way_landing(&self, rect: Rect, from_bottom: f32, to_bottom: f32) -> Option<f32> {
+        let start_x = (rect.x / TILE_SIZE).floor().max(0.0) as usize;
+        let end_x = (((rect.x + rect.width) / TILE_SIZE).ceil().max(0.0) as usize).min(self.width);
+        let start_y = ((from_bottom - ONE_WAY_TOLERANCE) / TILE_SIZE).ceil().max(0.0) as usize;
+
+        for y in start_y..self.height {
+            let top = y as f32 * TILE_SIZE;
+            if top > to_bottom {
+                break;
+            }
+            if (start_x..end_x).any(|x| self.tiles[y][x].one_way) {
+                return Some(top);
+            }
+        }
+        None
+    }
+
+    /// Build the city behind the level from a seed: far and mid skylines, a street-level block and street props.
+    /// Backdrops only span as far as their parallax lets the camera scroll across them.
+    pub fn generate_city(&mut self, seed: u64) {
+        let level_width = self.width as f32 * TILE_SIZE;
+        let ground_y = (self.height - 3) as f32 * TILE_SIZE;
+        let span = |layer: RenderLayer| level_width * layer.default_parallax().scroll_factor_x + CITY_VIEW_MARGIN;
+        let generator = BuildingGenerator::new(seed);
+
+        self.background_buildings = vec![
+            generator.generate_skyline(span(RenderLayer::FarBackground), ground_y, 2),
+            generator.generate_skyline(span(RenderLayer::MidBackground), ground_y, 1),
+        ];
+        self.buildings = generator.generate_city_block(0.0, span(RenderLayer::NearBackground), ground_y, 0.6);
+
+        // Keep props off hills, ramps and barriers standing on the street
+        let street_row = self.height - 3;
+        let props = generator.generate_street_props(level_width, ground_y);
+        self.street_props = props
+            .into_iter()
+            .filter(|prop| {
+                let first = (prop.position.x / TILE_SIZE).floor().max(0.0) as usize;
+                let last = ((prop.position.x + prop.size.x) / TILE_SIZE) as usize;
+                let rows = (prop.size.y / TILE_SIZE).ceil() as usize;
+                (first..=last).all(|x| {
+                    (1..=rows).all(|up| {
+                        street_row
+                            .checked_sub(up)
+                            .and_then(|y| self.get_tile(x, y))
+                            .filter(|tile| tile.tile_type != TileType::Empty)
+                            .is_none()
+                    })
+                })
+            })
+            .collect();
+    }
+
+    /// Re-arm every trigger for a fresh attempt at the level
+    pub fn reset_triggers(&mut self) {
+        for trigger in &mut self.triggers {
+            trigger.reset();
+        }
+    }
+
     pub fn test_level_1() -> Self {
         // Create a much wider level - assuming ~40 pixels per meter, 2000m = 80,000 pixels / 32 tile size = 2500 tiles
         let width_in_tiles = 2500;
@@ -224,6 +492,26 @@ impl Level {
                     }
                 }
             }
+            if i >= 1 {
+                // Rubble barricades a heavy mech can walk straight through
+                for j in 0..10 {
+                    let barricade_pos = 1300 + j * 110;
+                    if barricade_pos + 2 < width_in_tiles {
+                        row.replace_range(barricade_pos..barricade_pos+2, "DD");
+                    }
+                }
+            }
+            if i == 3 {
+                // Gentle hills and steep ramps along the street
+                for j in 0..4 {
+                    let hill_pos = 150 + j * 300;
+                    row.replace_range(hill_pos..hill_pos+8, "rR####Ll");
+                    let ramp_pos = 250 + j * 300;
+                    row.replace_range(ramp_pos..ramp_pos+4, "/##\\");
+                }
+                // Low concrete barriers to take cover behind
+                row.replace_range(1260..1262, "__");
+            }
             level_str.push_str(&row);
             level_str.push('\n');
         }
@@ -236,8 +524,21 @@ impl Level {
         
         let mut level = Self::from_string(&level_str);
         level.name = "Level 1: First Contact".to_string();
+        level.generate_city(101);
         level.description = "Defeat all enemy forces".to_string();
         level.win_condition = WinCondition::DefeatAllEnemies;
+
+        // Walking into the rubble field sets off an ambush from the far side
+        level.triggers.push(
+            TriggerVolume::new("barricade_ambush", tile_area(1290, 0, 4, 19), TriggerEvent::Enter)
+                .with_action(TriggerAction::ShowMessage { text: "AMBUSH!".to_string(), duration: 2.0 })
+                .with_action(TriggerAction::SpawnWave {
+                    position: Vec2::new(1340.0 * TILE_SIZE, 15.0 * TILE_SIZE),
+                    count: 5,
+                    spread: 160.0,
+                }),
+        );
+        level.checkpoints = vec![checkpoint_on_ground(1280, 20), checkpoint_on_ground(1600, 20)];
         level
     }
     
@@ -247,20 +548,21 @@ impl Level {
         
         // Build level with spawn on left, goal on right
         for y in 0..20 {
-            let mut row = String::new();
-            for x in 0..width_in_tiles {
-                if y == 15 && x == 10 {
-                    row.push('S'); // Spawn point
-                } else if y >= 17 {
-                    row.push('#'); // Ground
-                } else if y == 16 && x % 50 == 0 && x > 0 && x < width_in_tiles - 20 {
+            let mut row = ".".repeat(width_in_tiles);
+            match y {
+                15 => row.replace_range(10..11, "S"), // Spawn point
+                16 => {
                     // Platforms every 50 tiles
-                    for _ in 0..10 {
-                        row.push('=');
+                    for x in (50..width_in_tiles - 20).step_by(50) {
+                        row.replace_range(x..x + 10, "==========");
+                    }
+                    // Rolling hills between the platforms
+                    for x in (25..width_in_tiles).step_by(50) {
+                        row.replace_range(x..x + 6, "rR##Ll");
                     }
-                } else {
-                    row.push('.');
                 }
+                17.. => row = "#".repeat(width_in_tiles), // Ground
+                _ => {}
             }
             level_str.push_str(&row);
             level_str.push('\n');
@@ -268,12 +570,36 @@ impl Level {
         
         let mut level = Self::from_string(&level_str);
         level.name = "Level 2: The Journey".to_string();
+        level.generate_city(202);
         level.description = "Reach the extraction point".to_string();
         level.goal_position = Some(Vec2::new((width_in_tiles - 20) as f32 * TILE_SIZE, 15.0 * TILE_SIZE));
         level.win_condition = WinCondition::ReachGoal {
             position: level.goal_position.unwrap(),
             radius: 50.0,
         };
+
+        // A gate slams shut behind the player as a squad drops in, and opens again once they push on
+        level.triggers.push(
+            TriggerVolume::new("gate_ambush", tile_area(150, 0, 2, 17), TriggerEvent::Enter)
+                .with_action(TriggerAction::SetTiles { x: 140, y: 11, width: 1, height: 5, tile_type: TileType::Wall })
+                .with_action(TriggerAction::ShowMessage { text: "THE GATE IS CLOSING".to_string(), duration: 2.5 })
+                .with_action(TriggerAction::FrameCamera {
+                    points: vec![Vec2::new(140.0 * TILE_SIZE, 14.0 * TILE_SIZE), Vec2::new(170.0 * TILE_SIZE, 15.0 * TILE_SIZE)],
+                    duration: 2.5,
+                })
+                .with_action(TriggerAction::SpawnWave {
+                    position: Vec2::new(170.0 * TILE_SIZE, 15.0 * TILE_SIZE),
+                    count: 4,
+                    spread: 120.0,
+                }),
+        );
+        level.triggers.push(
+            TriggerVolume::new("gate_release", tile_area(190, 0, 2, 17), TriggerEvent::Enter)
+                .with_condition(TriggerCondition::AfterTrigger("gate_ambush".to_string()))
+                .with_action(TriggerAction::SetTiles { x: 140, y: 11, width: 1, height: 5, tile_type: TileType::Empty }),
+        );
+        // The first flag sits past the gate so a respawn never lands behind it once it has shut
+        level.checkpoints = vec![checkpoint_on_ground(160, 17), checkpoint_on_ground(240, 17), checkpoint_on_ground(360, 17)];
         level
     }
     
@@ -283,20 +609,23 @@ impl Level {
         
         // Build arena-style level
         for y in 0..20 {
-            let mut row = String::new();
-            for x in 0..width_in_tiles {
-                if y == 15 && x == width_in_tiles / 2 {
-                    row.push('S'); // Spawn in center
-                } else if y >= 17 {
-                    row.push('#'); // Ground
-                } else if y == 14 && (x == 50 || x == width_in_tiles - 50) {
+            let mut row = ".".repeat(width_in_tiles);
+            match y {
+                14 => {
                     // Side platforms
-                    for _ in 0..20 {
-                        row.push('=');
+                    for x in [50, width_in_tiles - 50] {
+                        row.replace_range(x..x + 20, &"=".repeat(20));
+                    }
+                }
+                15 => row.replace_range(width_in_tiles / 2..width_in_tiles / 2 + 1, "S"), // Spawn in center
+                16 => {
+                    // Ramped bunkers flanking the spawn
+                    for x in [100, width_in_tiles - 100] {
+                        row.replace_range(x..x + 4, "/##\\");
                     }
-                } else {
-                    row.push('.');
                 }
+                17.. => row = "#".repeat(width_in_tiles), // Ground
+                _ => {}
             }
             level_str.push_str(&row);
             level_str.push('\n');
@@ -304,15 +633,61 @@ impl Level {
         
         let mut level = Self::from_string(&level_str);
         level.name = "Level 3: Last Stand".to_string();
+        level.generate_city(303);
         level.description = "Survive the enemy assault for 2 minutes".to_string();
         level.win_condition = WinCondition::SurviveTime { duration: 120.0 };
+        // Dying restarts the clock
+        level.respawn_rules.keep_time = false;
+
+        // Short briefing before the first wave
+        let spawn = level.spawn_point;
+        level.triggers.push(
+            TriggerVolume::new("briefing", Rect::new(spawn.x - 64.0, spawn.y - 64.0, 128.0, 128.0), TriggerEvent::Enter)
+                .with_subject(TriggerSubject::Player)
+                .with_action(TriggerAction::StartCutscene {
+                    caption: "HOLD THIS POSITION. THEY ARE COMING.".to_string(),
+                    duration: 3.0,
+                }),
+        );
         level
     }
     
     pub fn level_4_collect() -> Self {
         let width_in_tiles = 800;
-        let mut level = Self::test_level_1(); // Base it on level 1 layout
+        let mut level_str = String::new();
+
+        // Open street with raised walkways holding the two hardest-to-reach cores
+        for y in 0..20 {
+            let mut row = ".".repeat(width_in_tiles);
+            match y {
+                11 => {
+                    for x in [295, 495] {
+                        row.replace_range(x..x + 10, "==========");
+                    }
+                }
+                14 => {
+                    // Steps up to the walkways
+                    for x in [285, 485] {
+                        row.replace_range(x..x + 5, "=====");
+                    }
+                }
+                15 => row.replace_range(50..51, "S"),
+                16 => {
+                    // Rolling hills between the cores
+                    for x in (250..width_in_tiles - 50).step_by(200) {
+                        row.replace_range(x..x + 6, "rR##Ll");
+                    }
+                }
+                17.. => row = "#".repeat(width_in_tiles),
+                _ => {}
+            }
+            level_str.push_str(&row);
+            level_str.push('\n');
+        }
+
+        let mut level = Self::from_string(&level_str);
         level.name = "Level 4: Scavenger Hunt".to_string();
+        level.generate_city(404);
         level.description = "Collect 5 power cores".to_string();
         
         // Add collectibles at various positions
@@ -325,12 +700,16 @@ impl Level {
         ];
         
         level.win_condition = WinCondition::CollectItems { required: 5 };
+        level.checkpoints = vec![checkpoint_on_ground(350, 17), checkpoint_on_ground(550, 17)];
+        // Cores grabbed since the last checkpoint are dropped on death
+        level.respawn_rules.keep_items = false;
         level
     }
     
     pub fn level_5_compound() -> Self {
         let mut level = Self::level_2_reach_goal();
         level.name = "Level 5: Multi-Objective".to_string();
+        level.generate_city(505);
         level.description = "Complete all objectives".to_string();
         
         // Add collectibles
@@ -354,3 +733,23 @@ impl Level {
         level
     }
 }
+
+/// Just the type of every tile, row by row
+fn tile_types(tiles: &[Vec<Tile>]) -> Vec<Vec<TileType>> {
+    tiles.iter().map(|row| row.iter().map(|tile| tile.tile_type).collect()).collect()
+}
+
+/// World rect covering a block of tiles
+fn tile_area(x: usize, y: usize, width: usize, height: usize) -> Rect {
+    Rect::new(
+        x as f32 * TILE_SIZE,
+        y as f32 * TILE_SIZE,
+        width as f32 * TILE_SIZE,
+        height as f32 * TILE_SIZE,
+    )
+}
+
+/// Checkpoint flag standing on the top of ground row `ground_y` at column `x`
+fn checkpoint_on_ground(x: usize, ground_y: usize) -> Vec2 {
+    Vec2::new((x as f32 + 0.5) * TILE_SIZE, ground_y as f32 * TILE_SIZE - TILE_SIZE)
+}
@@ -2,12 +2,27 @@ use crate::game::level::Level;
 use crate::game::win_condition::{WinCondition, WinProgress};
 use glam::Vec2;
 
+/// Objective progress when the last checkpoint was reached, restored on death according to the level's rules
+#[derive(Debug, Clone)]
+struct CheckpointProgress {
+    items_collected: usize,
+    collected: Vec<bool>,
+    level_time: f32,
+}
+
 pub struct LevelManager {
     levels: Vec<Level>,
     current_level_index: usize,
+    /// Furthest level reached so far
+    unlocked_level: usize,
     level_time_elapsed: f32,
     items_collected: usize,
     boss_defeated: bool,
+    /// Set by a trigger that finishes the objective outright
+    objective_completed: bool,
+    checkpoint: Option<Vec2>,
+    active_checkpoint: Option<usize>,
+    checkpoint_progress: Option<CheckpointProgress>,
     level_complete: bool,
     all_levels_complete: bool,
 }
@@ -25,9 +40,14 @@ impl LevelManager {
         Self {
             levels,
             current_level_index: 0,
+            unlocked_level: 0,
             level_time_elapsed: 0.0,
             items_collected: 0,
             boss_defeated: false,
+            objective_completed: false,
+            checkpoint: None,
+            active_checkpoint: None,
+            checkpoint_progress: None,
             level_complete: false,
             all_levels_complete: false,
         }
@@ -53,13 +73,17 @@ impl LevelManager {
         enemy_count: usize,
     ) -> (bool, WinProgress) {
         let level = &self.levels[self.current_level_index];
-        let (complete, progress) = level.win_condition.check_completion(
-            player_pos,
-            enemy_count,
-            self.level_time_elapsed,
-            self.items_collected,
-            self.boss_defeated,
-        );
+        let (complete, progress) = if self.objective_completed {
+            (true, WinProgress::Complete)
+        } else {
+            level.win_condition.check_completion(
+                player_pos,
+                enemy_count,
+                self.level_time_elapsed,
+                self.items_collected,
+                self.boss_defeated,
+            )
+        };
         
         if complete && !self.level_complete {
             self.level_complete = true;
@@ -81,10 +105,84 @@ impl LevelManager {
     pub fn defeat_boss(&mut self) {
         self.boss_defeated = true;
     }
+
+    /// Count the current level's objective as done regardless of its win condition
+    pub fn complete_objective(&mut self) {
+        self.objective_completed = true;
+    }
+
+    /// Respawn at `position` from now on, remembering the objective progress made so far
+    pub fn set_checkpoint(&mut self, position: Vec2) {
+        let level = &self.levels[self.current_level_index];
+        self.checkpoint = Some(position);
+        self.checkpoint_progress = Some(CheckpointProgress {
+            items_collected: self.items_collected,
+            collected: level.collectibles.iter().map(|(_, collected)| *collected).collect(),
+            level_time: self.level_time_elapsed,
+        });
+    }
+
+    /// Make one of the level's checkpoint flags the respawn point. False if it, or a later
+    /// flag, already was - walking back past an earlier flag doesn't move the respawn point back.
+    pub fn activate_checkpoint(&mut self, index: usize) -> bool {
+        if self.active_checkpoint.is_some_and(|active| index <= active) {
+            return false;
+        }
+        let Some(&position) = self.levels[self.current_level_index].checkpoints.get(index) else {
+            return false;
+        };
+        self.active_checkpoint = Some(index);
+        self.set_checkpoint(position);
+        true
+    }
+
+    pub fn get_active_checkpoint(&self) -> Option<usize> {
+        self.active_checkpoint
+    }
+
+    /// Roll objective progress back to the last checkpoint as far as the level's respawn rules say.
+    /// Returns true when collectibles were put back and need respawning.
+    pub fn on_player_death(&mut self) -> bool {
+        let level = &mut self.levels[self.current_level_index];
+        let rules = level.respawn_rules;
+
+        if !rules.keep_time {
+            self.level_time_elapsed = self.checkpoint_progress.as_ref().map_or(0.0, |p| p.level_time);
+        }
+
+        if rules.keep_items {
+            return false;
+        }
+        match &self.checkpoint_progress {
+            Some(progress) => {
+                self.items_collected = progress.items_collected;
+                let mut restored_any = false;
+                for (collectible, &was_collected) in level.collectibles.iter_mut().zip(&progress.collected) {
+                    restored_any |= collectible.1 && !was_collected;
+                    collectible.1 = was_collected;
+                }
+                restored_any
+            }
+            None => {
+                self.items_collected = 0;
+                let restored_any = level.collectibles.iter().any(|(_, collected)| *collected);
+                for collectible in &mut level.collectibles {
+                    collectible.1 = false;
+                }
+                restored_any
+            }
+        }
+    }
+
+    /// Last checkpoint reached, or the level's spawn point
+    pub fn get_respawn_point(&self) -> Vec2 {
+        self.checkpoint.unwrap_or(self.levels[self.current_level_index].spawn_point)
+    }
     
     pub fn next_level(&mut self) -> bool {
         if self.current_level_index < self.levels.len() - 1 {
             self.current_level_index += 1;
+            self.unlocked_level = self.unlocked_level.max(self.current_level_index);
             self.reset_level_state();
             true
         } else {
@@ -101,13 +199,19 @@ impl LevelManager {
         self.level_time_elapsed = 0.0;
         self.items_collected = 0;
         self.boss_defeated = false;
+        self.objective_completed = false;
+        self.checkpoint = None;
+        self.active_checkpoint = None;
+        self.checkpoint_progress = None;
         self.level_complete = false;
         
-        // Reset collectibles
+        // Reset collectibles, re-arm triggers and undo tile edits
         let level = &mut self.levels[self.current_level_index];
         for collectible in &mut level.collectibles {
             collectible.1 = false;
         }
+        level.reset_triggers();
+        level.reset_tiles();
     }
     
     pub fn go_to_level(&mut self, index: usize) -> bool {
@@ -120,6 +224,19 @@ impl LevelManager {
         }
     }
     
+    /// Pick a saved campaign back up at `current`, with every level up to `unlocked` reachable
+    pub fn restore_progress(&mut self, current: usize, unlocked: usize) {
+        let last = self.levels.len() - 1;
+        self.unlocked_level = unlocked.min(last);
+        self.current_level_index = current.min(self.unlocked_level);
+        self.all_levels_complete = false;
+        self.reset_level_state();
+    }
+
+    pub fn get_unlocked_level(&self) -> usize {
+        self.unlocked_level
+    }
+
     pub fn is_level_complete(&self) -> bool {
         self.level_complete
     }
@@ -139,8 +256,18 @@ impl LevelManager {
     pub fn get_level_time(&self) -> f32 {
         self.level_time_elapsed
     }
+
+    pub fn get_items_collected(&self) -> usize {
+        self.items_collected
+    }
     
     pub fn get_collectibles_status(&self) -> Vec<(Vec2, bool)> {
         self.levels[self.current_level_index].collectibles.clone()
     }
-}
\ No newline at end of file
+}
+
+impl Default for LevelManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,177 @@
+use crate::engine::core::Color;
+use crate::engine::platform::InputState;
+use crate::engine::rendering::Renderer;
+use crate::engine::ui::Anchor;
+use crate::game::save::{parse_saved_at, SlotSummary, SAVE_SLOTS};
+use glam::Vec2;
+use sdl2::keyboard::Keycode;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MenuAction {
+    None,
+    /// Resume a filled slot on a level it has unlocked
+    Continue { slot: usize, level: usize },
+    /// Start over in a slot, replacing whatever was there
+    NewGame { slot: usize },
+}
+
+/// Title screen listing the save slots
+pub struct MainMenu {
+    pub is_open: bool,
+    slots: Vec<Option<SlotSummary>>,
+    selected: usize,
+    /// Level chosen for the selected slot
+    level: usize,
+    /// Waiting for a yes before a new game replaces the selected slot
+    confirm_overwrite: bool,
+    screen_size: Vec2,
+}
+
+impl MainMenu {
+    pub fn new(screen_width: f32, screen_height: f32) -> Self {
+        Self {
+            is_open: false,
+            slots: vec![None; SAVE_SLOTS],
+            selected: 0,
+            level: 0,
+            confirm_overwrite: false,
+            screen_size: Vec2::new(screen_width, screen_height),
+        }
+    }
+
+    pub fn resize(&mut self, screen_size: Vec2) {
+        self.screen_size = screen_size;
+    }
+
+    /// Show the menu with fresh slot info, highlighting the slot to continue
+    pub fn open(&mut self, slots: Vec<Option<SlotSummary>>, latest: Option<usize>) {
+        self.is_open = true;
+        self.slots = slots;
+        self.select(latest.unwrap_or(0));
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+    }
+
+    fn select(&mut self, slot: usize) {
+        self.selected = slot;
+        self.confirm_overwrite = false;
+        self.level = self.slots[slot].as_ref().map_or(0, |summary| summary.current_level);
+    }
+
+    pub fn handle_input(&mut self, input: &InputState) -> MenuAction {
+        let slot_count = self.slots.len();
+
+        if self.confirm_overwrite {
+            if input.is_key_pressed(Keycode::Y) {
+                self.confirm_overwrite = false;
+                return MenuAction::NewGame { slot: self.selected };
+            }
+            if input.is_key_pressed(Keycode::N) {
+                self.confirm_overwrite = false;
+            }
+            return MenuAction::None;
+        }
+
+        if input.is_key_pressed(Keycode::Up) || input.is_key_pressed(Keycode::W) {
+            self.select((self.selected + slot_count - 1) % slot_count);
+        }
+        if input.is_key_pressed(Keycode::Down) || input.is_key_pressed(Keycode::S) {
+            self.select((self.selected + 1) % slot_count);
+        }
+
+        // Replay any level the slot has reached
+        if let Some(summary) = &self.slots[self.selected] {
+            if input.is_key_pressed(Keycode::Left) || input.is_key_pressed(Keycode::A) {
+                self.level = self.level.saturating_sub(1);
+            }
+            if input.is_key_pressed(Keycode::Right) || input.is_key_pressed(Keycode::D) {
+                self.level = (self.level + 1).min(summary.unlocked_level);
+            }
+        }
+
+        if input.is_key_pressed(Keycode::N) {
+            // Starting over in a filled slot throws that campaign away, so ask first
+            if self.slots[self.selected].is_some() {
+                self.confirm_overwrite = true;
+                return MenuAction::None;
+            }
+            return MenuAction::NewGame { slot: self.selected };
+        }
+        if input.is_key_pressed(Keycode::Return) || input.is_key_pressed(Keycode::Space) {
+            return match self.slots[self.selected] {
+                Some(_) => MenuAction::Continue { slot: self.selected, level: self.level },
+                None => MenuAction::NewGame { slot: self.selected },
+            };
+        }
+
+        MenuAction::None
+    }
+
+    pub fn render(&self, renderer: &mut Renderer) {
+        if !self.is_open {
+            return;
+        }
+
+        renderer.draw_ui_rect(Vec2::ZERO, self.screen_size, Color::new(10, 12, 20, 255));
+
+        let panel_size = Vec2::new(640.0, 400.0);
+        let panel_pos = Anchor::Center.calculate_position(self.screen_size, panel_size, Vec2::ZERO);
+        renderer.draw_ui_rect(panel_pos, panel_size, Color::new(20, 20, 30, 230));
+        renderer.draw_ui_rect_outline(panel_pos, panel_size, Color::new(100, 150, 200, 255), 2.0);
+
+        renderer.draw_ui_text(
+            panel_pos + Vec2::new(20.0, 20.0),
+            "LEGENDS OF LEGEND",
+            Color::new(100, 200, 255, 255),
+            24,
+        );
+
+        for (slot, summary) in self.slots.iter().enumerate() {
+            let row_pos = panel_pos + Vec2::new(20.0, 90.0 + slot as f32 * 60.0);
+            let selected = slot == self.selected;
+
+            if selected {
+                renderer.draw_ui_rect(row_pos - Vec2::new(8.0, 8.0), Vec2::new(600.0, 46.0), Color::new(50, 60, 100, 200));
+                renderer.draw_ui_rect_outline(row_pos - Vec2::new(8.0, 8.0), Vec2::new(600.0, 46.0), Color::new(100, 150, 255, 255), 1.0);
+            }
+
+            renderer.draw_ui_text(row_pos, &format!("SLOT {}", slot + 1), Color::new(160, 160, 180, 255), 16);
+            let (line, detail) = match summary {
+                Some(summary) => {
+                    let level = if selected { self.level } else { summary.current_level };
+                    let line = if selected {
+                        format!("CONTINUE  < LEVEL {} >", level + 1)
+                    } else {
+                        format!("LEVEL {}", level + 1)
+                    };
+                    // Stored in UTC, shown in local time without the seconds
+                    let saved = parse_saved_at(&summary.saved_at)
+                        .map(|saved_at| saved_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_default();
+                    (line, format!("REACHED LEVEL {}   {}", summary.unlocked_level + 1, saved))
+                }
+                None => ("NEW GAME".to_string(), "EMPTY".to_string()),
+            };
+            renderer.draw_ui_text(row_pos + Vec2::new(110.0, 0.0), &line, Color::WHITE, 16);
+            renderer.draw_ui_text(row_pos + Vec2::new(110.0, 20.0), &detail, Color::new(120, 120, 140, 255), 12);
+        }
+
+        if self.confirm_overwrite {
+            renderer.draw_ui_text(
+                panel_pos + Vec2::new(20.0, panel_size.y - 40.0),
+                &format!("REPLACE SLOT {} WITH A NEW GAME?   Y YES   N NO", self.selected + 1),
+                Color::new(255, 200, 80, 255),
+                14,
+            );
+        } else {
+            renderer.draw_ui_text(
+                panel_pos + Vec2::new(20.0, panel_size.y - 40.0),
+                "UP/DOWN SLOT   LEFT/RIGHT LEVEL   ENTER PLAY   N NEW GAME",
+                Color::new(200, 200, 200, 255),
+                14,
+            );
+        }
+    }
+}
@@ -1,15 +1,27 @@
 pub mod buildings;
 pub mod day_night_cycle;
+pub mod hangar;
 pub mod level;
 pub mod level_manager;
+pub mod main_menu;
+pub mod pathfinding;
+pub mod save;
 pub mod states;
+pub mod tile_chunks;
+pub mod trigger;
 pub mod ui_manager;
 pub mod win_condition;
 
 pub use buildings::*;
 pub use day_night_cycle::*;
+pub use hangar::*;
 pub use level::*;
 pub use level_manager::*;
+pub use main_menu::*;
+pub use pathfinding::*;
+pub use save::*;
 pub use states::*;
+pub use tile_chunks::*;
+pub use trigger::*;
 pub use ui_manager::*;
 pub use win_condition::*;
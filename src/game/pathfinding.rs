@@ -0,0 +1,177 @@
+use crate::game::level::{Level, TILE_SIZE};
+use glam::Vec2;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Upper bound on explored cells so long levels don't stall a frame
+const MAX_SEARCH_NODES: usize = 6000;
+/// How far sideways a jump can carry a walker, in tiles
+const MAX_JUMP_REACH: i32 = 3;
+
+type Cell = (usize, usize);
+
+/// A cell a walker can stand in: empty itself with a solid tile or one-way platform directly below
+pub fn is_standable(level: &Level, x: usize, y: usize) -> bool {
+    let empty = level.get_tile(x, y).is_some_and(|tile| !tile.solid);
+    let supported = level.get_tile(x, y + 1).is_some_and(|tile| tile.solid || tile.one_way);
+    empty && supported
+}
+
+/// A ramp, which walkers climb without jumping
+fn is_ramp(level: &Level, x: usize, y: usize) -> bool {
+    level.get_tile(x, y).is_some_and(|tile| tile.solid && tile.shape.is_ramp())
+}
+
+/// Standing on a one-way platform, which a walker can drop through
+fn on_platform(level: &Level, x: usize, y: usize) -> bool {
+    level.get_tile(x, y + 1).is_some_and(|tile| tile.one_way)
+}
+
+/// Grid cell containing the feet of a walker centred at `position`
+pub fn feet_cell(position: Vec2, height: f32) -> Cell {
+    let feet = position + Vec2::new(0.0, height / 2.0 - 1.0);
+    (
+        (feet.x / TILE_SIZE).max(0.0) as usize,
+        (feet.y / TILE_SIZE).max(0.0) as usize,
+    )
+}
+
+/// World position of the feet of a walker standing in a cell
+pub fn cell_feet_position(cell: Cell) -> Vec2 {
+    Vec2::new(
+        (cell.0 as f32 + 0.5) * TILE_SIZE,
+        (cell.1 + 1) as f32 * TILE_SIZE,
+    )
+}
+
+/// Drop straight down from a cell to the first standable cell below it
+fn land_below(level: &Level, x: usize, mut y: usize) -> Option<Cell> {
+    while y + 1 < level.height {
+        if is_standable(level, x, y) {
+            return Some((x, y));
+        }
+        if level.get_tile(x, y + 1).is_none_or(|tile| tile.solid) {
+            return None;
+        }
+        y += 1;
+    }
+    None
+}
+
+/// True when every cell in a column between two rows is open
+fn column_clear(level: &Level, x: usize, from_y: usize, to_y: usize) -> bool {
+    (to_y.min(from_y)..=to_y.max(from_y)).all(|y| level.get_tile(x, y).is_some_and(|tile| !tile.solid))
+}
+
+fn neighbours(level: &Level, (x, y): Cell, max_jump_tiles: usize) -> Vec<(Cell, u32)> {
+    let mut result = Vec::new();
+
+    for dx in [-1i32, 1] {
+        let nx = x as i32 + dx;
+        if nx < 0 || nx as usize >= level.width {
+            continue;
+        }
+        let nx = nx as usize;
+
+        // Walk along the floor, up onto a ramp or step off a ledge
+        if is_standable(level, nx, y) {
+            result.push(((nx, y), 10));
+        } else if y > 0 && is_ramp(level, nx, y) && is_standable(level, nx, y - 1) {
+            result.push(((nx, y - 1), 12));
+        } else if level.get_tile(nx, y).is_some_and(|tile| !tile.solid) {
+            if let Some(landing) = land_below(level, nx, y) {
+                result.push((landing, 10 + (landing.1 - y) as u32 * 4));
+            }
+        }
+    }
+
+    // Drop through a one-way platform to whatever is below it
+    if on_platform(level, x, y) {
+        if let Some(landing) = land_below(level, x, y + 1) {
+            result.push((landing, 15 + (landing.1 - y) as u32 * 4));
+        }
+    }
+
+    // Jump up onto higher ledges within reach (one-way platforms don't block the way up)
+    for dy in 1..=max_jump_tiles {
+        if y < dy || !column_clear(level, x, y - dy, y) {
+            break;
+        }
+        let ny = y - dy;
+        for dx in -MAX_JUMP_REACH..=MAX_JUMP_REACH {
+            let nx = x as i32 + dx;
+            if dx == 0 || nx < 0 || nx as usize >= level.width {
+                continue;
+            }
+            let nx = nx as usize;
+            if is_standable(level, nx, ny) && column_clear(level, nx, ny.saturating_sub(1), ny) {
+                result.push(((nx, ny), 20 + dy as u32 * 10 + dx.unsigned_abs() * 5));
+            }
+        }
+    }
+
+    result
+}
+
+fn heuristic(a: Cell, b: Cell) -> u32 {
+    ((a.0 as i32 - b.0 as i32).unsigned_abs() + (a.1 as i32 - b.1 as i32).unsigned_abs()) * 10
+}
+
+/// A* search over standable tiles. Returns the feet positions to walk through, excluding the start.
+/// Falls back to the closest reachable cell when the goal itself can't be reached.
+pub fn find_path(level: &Level, start: Cell, goal: Cell, max_jump_tiles: usize) -> Option<Vec<Vec2>> {
+    let start = if is_standable(level, start.0, start.1) {
+        start
+    } else {
+        land_below(level, start.0, start.1)?
+    };
+    let goal = if is_standable(level, goal.0, goal.1) {
+        goal
+    } else {
+        land_below(level, goal.0, goal.1).unwrap_or(goal)
+    };
+
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<Cell, Cell> = HashMap::new();
+    let mut cost: HashMap<Cell, u32> = HashMap::new();
+    let mut closest = (heuristic(start, goal), start);
+
+    open.push(Reverse((heuristic(start, goal), start)));
+    cost.insert(start, 0);
+
+    let mut explored = 0;
+    while let Some(Reverse((_, current))) = open.pop() {
+        if current == goal {
+            closest = (0, goal);
+            break;
+        }
+        explored += 1;
+        if explored > MAX_SEARCH_NODES {
+            break;
+        }
+
+        let current_cost = cost[&current];
+        for (next, step_cost) in neighbours(level, current, max_jump_tiles) {
+            let next_cost = current_cost + step_cost;
+            if cost.get(&next).is_none_or(|&known| next_cost < known) {
+                cost.insert(next, next_cost);
+                came_from.insert(next, current);
+                let estimate = heuristic(next, goal);
+                if estimate < closest.0 {
+                    closest = (estimate, next);
+                }
+                open.push(Reverse((next_cost + estimate, next)));
+            }
+        }
+    }
+
+    // Walk back from the goal (or the best we found) to the start
+    let mut path = Vec::new();
+    let mut current = closest.1;
+    while current != start {
+        path.push(cell_feet_position(current));
+        current = *came_from.get(&current)?;
+    }
+    path.reverse();
+    Some(path)
+}
@@ -0,0 +1,360 @@
+use crate::game::LevelManager;
+use crate::systems::mech::MechLoadout;
+use crate::systems::respawn_system::{Lives, STARTING_CONTINUES, STARTING_LIVES};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Current save format. Bump it when the layout changes and teach `migrate` to read the old one.
+pub const SAVE_VERSION: u32 = 1;
+pub const SAVE_SLOTS: usize = 3;
+/// Points the data directory somewhere else (tests, portable installs)
+pub const DATA_DIR_ENV: &str = "LEGENDS_DATA_DIR";
+const APP_DIR_NAME: &str = "legends-of-legend";
+/// Where the hangar loadout was kept before there were save files
+const LEGACY_LOADOUT_PATH: &str = "mech_loadout.ron";
+
+/// Player preferences stored alongside the campaign
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    pub show_debug_overlay: bool,
+    pub screen_shake: bool,
+    /// Draw at a fixed resolution scaled up by whole multiples
+    #[serde(default)]
+    pub pixel_scaling: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            show_debug_overlay: false,
+            screen_shake: true,
+            pixel_scaling: false,
+        }
+    }
+}
+
+/// Everything that carries over between runs
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    /// Level to resume on
+    pub current_level: usize,
+    /// Furthest level reached
+    pub unlocked_level: usize,
+    pub lives: u32,
+    pub continues: u32,
+    pub loadout: MechLoadout,
+    #[serde(default)]
+    pub settings: Settings,
+    /// When the slot was last written (RFC 3339, UTC)
+    #[serde(default)]
+    pub saved_at: String,
+}
+
+impl SaveGame {
+    /// A fresh campaign
+    pub fn new(loadout: MechLoadout) -> Self {
+        Self {
+            version: SAVE_VERSION,
+            current_level: 0,
+            unlocked_level: 0,
+            lives: STARTING_LIVES,
+            continues: STARTING_CONTINUES,
+            loadout,
+            settings: Settings::default(),
+            saved_at: String::new(),
+        }
+    }
+
+    /// Snapshot the running campaign
+    pub fn capture(level_manager: &LevelManager, lives: &Lives, loadout: &MechLoadout, settings: &Settings) -> Self {
+        Self {
+            version: SAVE_VERSION,
+            current_level: level_manager.get_current_level_index(),
+            unlocked_level: level_manager.get_unlocked_level(),
+            lives: lives.lives,
+            continues: lives.continues,
+            loadout: loadout.clone(),
+            settings: settings.clone(),
+            saved_at: String::new(),
+        }
+    }
+
+    pub fn lives(&self) -> Lives {
+        Lives {
+            lives: self.lives,
+            continues: self.continues,
+        }
+    }
+}
+
+/// Only the version, read first so the rest can be parsed the right way.
+/// Files without one are version 0.
+#[derive(Deserialize)]
+struct SaveHeader {
+    #[serde(default)]
+    version: u32,
+}
+
+/// Short description of a filled slot for the menu
+#[derive(Clone, Debug)]
+pub struct SlotSummary {
+    pub current_level: usize,
+    pub unlocked_level: usize,
+    pub saved_at: String,
+}
+
+/// Numbered save slots kept in one directory
+pub struct SaveStore {
+    dir: PathBuf,
+}
+
+impl SaveStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Saves in the user's data directory (or `LEGENDS_DATA_DIR` when set)
+    pub fn open_default() -> Self {
+        Self::new(data_dir())
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn slot_path(&self, slot: usize) -> PathBuf {
+        self.dir.join(format!("slot{}.ron", slot + 1))
+    }
+
+    pub fn save(&self, slot: usize, save: &SaveGame) -> Result<()> {
+        if slot >= SAVE_SLOTS {
+            bail!("no save slot {}", slot + 1);
+        }
+        fs::create_dir_all(&self.dir).with_context(|| format!("creating {}", self.dir.display()))?;
+
+        let mut save = save.clone();
+        save.version = SAVE_VERSION;
+        save.saved_at = chrono::Utc::now().to_rfc3339();
+        let contents = ron::ser::to_string_pretty(&save, ron::ser::PrettyConfig::default())?;
+        write_atomic(&self.slot_path(slot), contents.as_bytes())
+    }
+
+    pub fn load(&self, slot: usize) -> Result<SaveGame> {
+        let path = self.slot_path(slot);
+        let contents = fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+        migrate(&contents).with_context(|| format!("loading {}", path.display()))
+    }
+
+    /// What's in each slot, None for empty or unreadable ones
+    pub fn summaries(&self) -> Vec<Option<SlotSummary>> {
+        (0..SAVE_SLOTS)
+            .map(|slot| {
+                self.load(slot).ok().map(|save| SlotSummary {
+                    current_level: save.current_level,
+                    unlocked_level: save.unlocked_level,
+                    saved_at: save.saved_at,
+                })
+            })
+            .collect()
+    }
+
+    /// The most recently written slot, which "continue" resumes
+    pub fn latest_slot(&self) -> Option<usize> {
+        self.summaries()
+            .into_iter()
+            .enumerate()
+            // Compared as instants, so saves written under different UTC offsets still order correctly
+            .filter_map(|(slot, summary)| summary.map(|s| (slot, parse_saved_at(&s.saved_at))))
+            .max_by_key(|(_, saved_at)| *saved_at)
+            .map(|(slot, _)| slot)
+    }
+}
+
+/// When a slot was written, None if the timestamp is missing or unreadable
+pub fn parse_saved_at(saved_at: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(saved_at).ok()
+}
+
+/// Parse a save file of any known version into the current layout
+pub fn migrate(contents: &str) -> Result<SaveGame> {
+    let header: SaveHeader = ron::from_str(contents).context("not a save file")?;
+    let mut save: SaveGame = match header.version {
+        0 => upgrade_v0(contents)?,
+        SAVE_VERSION => ron::from_str(contents)?,
+        version => bail!("save version {} is newer than this build supports ({})", version, SAVE_VERSION),
+    };
+
+    // Module slots follow the chassis, whatever the file says
+    let chassis_id = save.loadout.chassis.clone();
+    save.loadout.set_chassis(&chassis_id);
+    save.version = SAVE_VERSION;
+    Ok(save)
+}
+
+/// Version 0 is the bare `MechLoadout` older builds kept in `mech_loadout.ron`,
+/// from before there were save slots. It becomes a fresh campaign with that loadout.
+fn upgrade_v0(contents: &str) -> Result<SaveGame> {
+    let loadout: MechLoadout = ron::from_str(contents).context("reading a version 0 save")?;
+    Ok(SaveGame::new(loadout))
+}
+
+/// The loadout file older builds left in the working directory, if there is one
+pub fn import_legacy_loadout() -> Option<MechLoadout> {
+    let path = Path::new(LEGACY_LOADOUT_PATH);
+    if !path.exists() {
+        return None;
+    }
+    let imported = fs::read_to_string(path)
+        .with_context(|| format!("reading {}", path.display()))
+        .and_then(|contents| migrate(&contents));
+    match imported {
+        Ok(save) => {
+            log::info!("Imported mech loadout from {}", path.display());
+            Some(save.loadout)
+        }
+        Err(e) => {
+            log::warn!("Ignoring old mech loadout ({})", e);
+            None
+        }
+    }
+}
+
+/// `$LEGENDS_DATA_DIR`, else the XDG data directory (`$XDG_DATA_HOME` or `~/.local/share`),
+/// else `%APPDATA%` on Windows, else the working directory
+pub fn data_dir() -> PathBuf {
+    let var = |name: &str| env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+
+    if let Some(dir) = var(DATA_DIR_ENV) {
+        return dir;
+    }
+    if let Some(dir) = var("XDG_DATA_HOME") {
+        return dir.join(APP_DIR_NAME);
+    }
+    if let Some(home) = var("HOME") {
+        return home.join(".local").join("share").join(APP_DIR_NAME);
+    }
+    if let Some(dir) = var("APPDATA") {
+        return dir.join(APP_DIR_NAME);
+    }
+    PathBuf::from(".")
+}
+
+/// Write beside the target and rename over it, so a crash never leaves half a save behind
+fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let temp_path = path.with_extension("ron.tmp");
+    {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+    }
+    fs::rename(&temp_path, path).with_context(|| format!("replacing {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::mech::{MechModule, MechWeaponType};
+
+    /// A store in its own empty temp directory
+    fn temp_store(name: &str) -> SaveStore {
+        let dir = std::env::temp_dir().join(format!("legends-save-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        SaveStore::new(dir)
+    }
+
+    /// Write a slot as-is, keeping its timestamp
+    fn write_slot(store: &SaveStore, slot: usize, save: &SaveGame) {
+        fs::create_dir_all(store.dir()).unwrap();
+        let contents = ron::ser::to_string_pretty(save, ron::ser::PrettyConfig::default()).unwrap();
+        fs::write(store.slot_path(slot), contents).unwrap();
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let store = temp_store("round-trip");
+        let mut save = SaveGame::new(MechLoadout::for_chassis("siege"));
+        save.current_level = 2;
+        save.unlocked_level = 3;
+        save.lives = 1;
+        save.loadout.modules[0] = Some(MechModule::Booster);
+        save.settings.screen_shake = false;
+
+        store.save(1, &save).unwrap();
+        let loaded = store.load(1).unwrap();
+
+        assert!(parse_saved_at(&loaded.saved_at).is_some());
+        assert_eq!(loaded, SaveGame { saved_at: loaded.saved_at.clone(), ..save });
+        assert!(store.load(0).is_err());
+        assert!(store.save(SAVE_SLOTS, &loaded).is_err());
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+
+    #[test]
+    fn migrates_a_bare_v0_loadout() {
+        let mut loadout = MechLoadout::for_chassis("titan");
+        loadout.primary = MechWeaponType::MissileLauncher;
+        loadout.modules[1] = Some(MechModule::EnergyCell);
+        let contents = ron::to_string(&loadout).unwrap();
+
+        let save = migrate(&contents).unwrap();
+
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.loadout, loadout);
+        assert_eq!(save.current_level, 0);
+        assert_eq!(save.lives, STARTING_LIVES);
+    }
+
+    #[test]
+    fn rejects_a_newer_version() {
+        let mut save = SaveGame::new(MechLoadout::default());
+        save.version = SAVE_VERSION + 1;
+        let contents = ron::to_string(&save).unwrap();
+
+        let err = migrate(&contents).unwrap_err();
+        assert!(err.to_string().contains("newer"));
+    }
+
+    #[test]
+    fn resizes_module_slots_to_the_chassis() {
+        let mut save = SaveGame::new(MechLoadout::for_chassis("scout"));
+        save.loadout.modules = vec![Some(MechModule::ArmorPlating), Some(MechModule::Booster), None];
+        let contents = ron::to_string(&save).unwrap();
+
+        let loaded = migrate(&contents).unwrap();
+        assert_eq!(loaded.loadout.modules, vec![Some(MechModule::ArmorPlating)]);
+
+        save.loadout = MechLoadout::for_chassis("siege");
+        save.loadout.modules = vec![];
+        let contents = ron::to_string(&save).unwrap();
+
+        let loaded = migrate(&contents).unwrap();
+        assert_eq!(loaded.loadout.modules, vec![None; 3]);
+    }
+
+    #[test]
+    fn latest_slot_is_the_most_recent_save() {
+        let store = temp_store("latest");
+        assert_eq!(store.latest_slot(), None);
+
+        // 10:00 UTC, though its text sorts after the other save's
+        let mut earlier = SaveGame::new(MechLoadout::default());
+        earlier.saved_at = "2024-05-01T12:00:00+02:00".to_string();
+        let mut later = earlier.clone();
+        later.saved_at = "2024-05-01T11:00:00+00:00".to_string();
+
+        write_slot(&store, 0, &earlier);
+        write_slot(&store, 1, &later);
+        assert_eq!(store.latest_slot(), Some(1));
+
+        store.save(0, &earlier).unwrap();
+        assert_eq!(store.latest_slot(), Some(0));
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+}
@@ -0,0 +1,313 @@
+use crate::engine::core::{Color, Rect, Transform};
+use crate::engine::rendering::{DrawCommand, RenderLayer, RenderQueue, Renderer, Sprite, TextureHandle};
+use crate::game::level::{Level, Tile, TileShape, TileType, TILE_SIZE};
+use glam::Vec2;
+use std::collections::HashMap;
+
+/// Tiles along each side of a cached chunk
+pub const CHUNK_TILES: usize = 16;
+/// Chunk size in world pixels
+const CHUNK_SIZE: f32 = CHUNK_TILES as f32 * TILE_SIZE;
+/// Chunk textures kept at once. Past this the chunk drawn least recently gives its texture up.
+const MAX_CHUNK_TEXTURES: usize = 160;
+
+const GRASS: Color = Color { r: 70, g: 130, b: 50, a: 255 };
+const GRASS_SHADOW: Color = Color { r: 50, g: 95, b: 40, a: 255 };
+
+#[derive(Debug, Clone)]
+struct CachedChunk {
+    /// None when the chunk has no tiles to draw
+    texture: Option<TextureHandle>,
+    stale: bool,
+    last_drawn: u64,
+}
+
+/// Draws the level's tiles as one cached texture per chunk instead of a rect per tile.
+/// Chunks are redrawn only when their tiles (or a neighbour's, for the borders) change.
+#[derive(Debug, Default)]
+pub struct TileChunkCache {
+    level_name: String,
+    chunks: HashMap<(usize, usize), CachedChunk>,
+    spare: Vec<TextureHandle>,
+    created: usize,
+    frame: u64,
+    /// Render targets aren't available, so tiles are drawn one rect at a time
+    unsupported: bool,
+}
+
+impl TileChunkCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark chunks out of date: all of them when the level changes, otherwise those around edited tiles
+    pub fn sync(&mut self, level: &mut Level) {
+        let edited = level.take_edited_tiles();
+        if level.name != self.level_name {
+            self.level_name = level.name.clone();
+            self.spare.extend(self.chunks.drain().filter_map(|(_, chunk)| chunk.texture));
+            return;
+        }
+
+        for (x, y) in edited {
+            // Neighbours' borders depend on this tile too
+            for (dx, dy) in [(0, 0), (-1, 0), (1, 0), (0, -1), (0, 1)] {
+                let (Some(nx), Some(ny)) = (x.checked_add_signed(dx), y.checked_add_signed(dy)) else {
+                    continue;
+                };
+                if let Some(chunk) = self.chunks.get_mut(&(nx / CHUNK_TILES, ny / CHUNK_TILES)) {
+                    chunk.stale = true;
+                }
+            }
+        }
+    }
+
+    /// Redraw every chunk the next time it's in view, e.g. after a restart or a lost render target
+    pub fn invalidate_all(&mut self) {
+        for chunk in self.chunks.values_mut() {
+            chunk.stale = true;
+        }
+    }
+
+    /// Rebuild any out of date chunks in view and queue them for drawing
+    pub fn submit(&mut self, queue: &mut RenderQueue, renderer: &mut Renderer, level: &Level) {
+        self.frame += 1;
+        let camera = &renderer.camera;
+        let half_view = camera.viewport_size / camera.zoom / 2.0;
+        let columns = level.width.div_ceil(CHUNK_TILES);
+        let rows = level.height.div_ceil(CHUNK_TILES);
+        let first = ((camera.position - half_view) / CHUNK_SIZE).floor().max(Vec2::ZERO);
+        let last = ((camera.position + half_view) / CHUNK_SIZE).floor();
+        let (min_x, min_y) = (first.x as usize, first.y as usize);
+        let max_x = (last.x.max(-1.0) as isize + 1).clamp(0, columns as isize) as usize;
+        let max_y = (last.y.max(-1.0) as isize + 1).clamp(0, rows as isize) as usize;
+
+        for chunk_y in min_y..max_y {
+            for chunk_x in min_x..max_x {
+                let key = (chunk_x, chunk_y);
+                let origin = Vec2::new(chunk_x as f32, chunk_y as f32) * CHUNK_SIZE;
+                let chunk = self.chunks.entry(key).or_insert(CachedChunk {
+                    texture: None,
+                    stale: true,
+                    last_drawn: 0,
+                });
+                chunk.last_drawn = self.frame;
+
+                if chunk.stale || self.unsupported {
+                    let rects = chunk_rects(level, chunk_x, chunk_y);
+                    if !self.rebuild(renderer, key, &rects) {
+                        // No texture to draw into, so fall back to a sprite per rect
+                        for (rect, color) in rects {
+                            let size = Vec2::new(rect.width, rect.height);
+                            let center = origin + Vec2::new(rect.x, rect.y) + size / 2.0;
+                            queue.submit(RenderLayer::Gameplay, Sprite::new(size, color), Transform::new(center));
+                        }
+                        continue;
+                    }
+                }
+
+                if let Some(texture) = self.chunks[&key].texture {
+                    let sprite = Sprite::new(Vec2::splat(CHUNK_SIZE), Color::WHITE).with_texture(texture);
+                    // Tiles sit behind everything else on the gameplay layer
+                    queue.push(
+                        DrawCommand::new(RenderLayer::Gameplay, sprite, Transform::new(origin + Vec2::splat(CHUNK_SIZE / 2.0)))
+                            .with_sort_key(f32::MIN),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Redraw a chunk's texture. False if there was no texture to draw it into.
+    fn rebuild(&mut self, renderer: &mut Renderer, key: (usize, usize), rects: &[(Rect, Color)]) -> bool {
+        if self.unsupported {
+            return false;
+        }
+        if rects.is_empty() {
+            let chunk = self.chunks.get_mut(&key).expect("chunk is cached before rebuilding");
+            self.spare.extend(chunk.texture.take());
+            chunk.stale = false;
+            return true;
+        }
+
+        let texture = match self.chunks[&key].texture {
+            Some(texture) => Some(texture),
+            None => self.allocate(renderer),
+        };
+        let Some(texture) = texture else {
+            return false;
+        };
+        if let Err(e) = renderer.fill_texture(texture, rects) {
+            log::warn!("Tile chunks unavailable, drawing tiles directly ({:#})", e);
+            self.unsupported = true;
+            return false;
+        }
+
+        let chunk = self.chunks.get_mut(&key).expect("chunk is cached before rebuilding");
+        chunk.texture = Some(texture);
+        chunk.stale = false;
+        true
+    }
+
+    /// A texture for a chunk: a spare one, a new one, or one taken from a chunk that's gone out of view
+    fn allocate(&mut self, renderer: &mut Renderer) -> Option<TextureHandle> {
+        if let Some(texture) = self.spare.pop() {
+            return Some(texture);
+        }
+        if self.created < MAX_CHUNK_TEXTURES {
+            match renderer.assets.create_target_texture(CHUNK_SIZE as u32, CHUNK_SIZE as u32) {
+                Ok(texture) => {
+                    self.created += 1;
+                    return Some(texture);
+                }
+                Err(e) => {
+                    log::warn!("Tile chunks unavailable, drawing tiles directly ({:#})", e);
+                    self.unsupported = true;
+                    return None;
+                }
+            }
+        }
+
+        let frame = self.frame;
+        let (&key, _) = self
+            .chunks
+            .iter()
+            .filter(|(_, chunk)| chunk.texture.is_some() && chunk.last_drawn < frame)
+            .min_by_key(|(_, chunk)| chunk.last_drawn)?;
+        self.chunks.remove(&key).and_then(|chunk| chunk.texture)
+    }
+}
+
+/// Everything to draw for one chunk, in pixels from its top-left corner
+fn chunk_rects(level: &Level, chunk_x: usize, chunk_y: usize) -> Vec<(Rect, Color)> {
+    let mut rects = Vec::new();
+    let start_x = chunk_x * CHUNK_TILES;
+    let start_y = chunk_y * CHUNK_TILES;
+    for y in start_y..(start_y + CHUNK_TILES).min(level.height) {
+        for x in start_x..(start_x + CHUNK_TILES).min(level.width) {
+            let offset = Vec2::new((x - start_x) as f32, (y - start_y) as f32) * TILE_SIZE;
+            tile_rects(level, x, y, offset, &mut rects);
+        }
+    }
+    rects
+}
+
+/// Which sides of a tile have no matching neighbour and need a border
+#[derive(Debug, Clone, Copy)]
+struct OpenSides {
+    top: bool,
+    right: bool,
+    bottom: bool,
+    left: bool,
+}
+
+/// Whether a tile blends into its neighbour without a border. The level's edges count as covered.
+fn connects(tile: &Tile, neighbour: Option<&Tile>) -> bool {
+    let Some(neighbour) = neighbour else {
+        return true;
+    };
+    match tile.tile_type {
+        TileType::Platform | TileType::Wall | TileType::Destructible => neighbour.tile_type == tile.tile_type,
+        _ => neighbour.solid,
+    }
+}
+
+fn open_sides(level: &Level, x: usize, y: usize, tile: &Tile) -> OpenSides {
+    let at = |dx: isize, dy: isize| -> Option<&Tile> {
+        let nx = x.checked_add_signed(dx)?;
+        let ny = y.checked_add_signed(dy)?;
+        level.get_tile(nx, ny)
+    };
+    OpenSides {
+        top: !connects(tile, at(0, -1)),
+        right: !connects(tile, at(1, 0)),
+        bottom: !connects(tile, at(0, 1)),
+        left: !connects(tile, at(-1, 0)),
+    }
+}
+
+/// Autotiled look of one tile: grassy tops on ground, capped platforms, bevelled walls and outlined breakables
+fn tile_rects(level: &Level, x: usize, y: usize, offset: Vec2, rects: &mut Vec<(Rect, Color)>) {
+    let Some(tile) = level.get_tile(x, y) else {
+        return;
+    };
+    if tile.tile_type == TileType::Empty {
+        return;
+    }
+    let open = open_sides(level, x, y, tile);
+    let base = tile.color;
+    let dark = base.lerp(Color::BLACK, 0.35);
+    let light = base.lerp(Color::WHITE, 0.25);
+    let mut fill = |x: f32, y: f32, width: f32, height: f32, color: Color| {
+        if width > 0.0 && height > 0.0 {
+            rects.push((Rect::new(offset.x + x, offset.y + y, width, height), color));
+        }
+    };
+
+    if let TileShape::Surface { .. } = tile.shape {
+        // Ramps and half blocks are drawn as one pixel columns following the surface
+        let top = if tile.tile_type == TileType::HalfBlock { light } else { GRASS };
+        for column in 0..TILE_SIZE as usize {
+            let column_x = column as f32;
+            let height = tile.shape.height_at(column_x + 0.5).round();
+            fill(column_x, TILE_SIZE - height, 1.0, height, base);
+            if open.top {
+                fill(column_x, TILE_SIZE - height, 1.0, height.min(4.0), top);
+            }
+        }
+        return;
+    }
+
+    fill(0.0, 0.0, TILE_SIZE, TILE_SIZE, base);
+    match tile.tile_type {
+        TileType::Platform => {
+            fill(0.0, 0.0, TILE_SIZE, 3.0, light);
+            fill(0.0, TILE_SIZE - 3.0, TILE_SIZE, 3.0, dark);
+            if open.left {
+                fill(0.0, 0.0, 3.0, TILE_SIZE, dark);
+            }
+            if open.right {
+                fill(TILE_SIZE - 3.0, 0.0, 3.0, TILE_SIZE, dark);
+            }
+        }
+        TileType::Wall => {
+            // Lit from the top left
+            if open.top {
+                fill(0.0, 0.0, TILE_SIZE, 2.0, light);
+            }
+            if open.left {
+                fill(0.0, 0.0, 2.0, TILE_SIZE, light);
+            }
+            if open.bottom {
+                fill(0.0, TILE_SIZE - 2.0, TILE_SIZE, 2.0, dark);
+            }
+            if open.right {
+                fill(TILE_SIZE - 2.0, 0.0, 2.0, TILE_SIZE, dark);
+            }
+        }
+        TileType::Destructible => {
+            // Every block is outlined so it reads as something that can be knocked out
+            fill(0.0, 0.0, TILE_SIZE, 2.0, dark);
+            fill(0.0, TILE_SIZE - 2.0, TILE_SIZE, 2.0, dark);
+            fill(0.0, 0.0, 2.0, TILE_SIZE, dark);
+            fill(TILE_SIZE - 2.0, 0.0, 2.0, TILE_SIZE, dark);
+            fill(10.0, 8.0, 2.0, 10.0, dark);
+            fill(12.0, 16.0, 8.0, 2.0, dark);
+        }
+        _ => {
+            if open.top {
+                fill(0.0, 0.0, TILE_SIZE, 4.0, GRASS);
+                fill(0.0, 4.0, TILE_SIZE, 2.0, GRASS_SHADOW);
+            }
+            if open.left {
+                fill(0.0, 0.0, 2.0, TILE_SIZE, dark);
+            }
+            if open.right {
+                fill(TILE_SIZE - 2.0, 0.0, 2.0, TILE_SIZE, dark);
+            }
+            if open.bottom {
+                fill(0.0, TILE_SIZE - 2.0, TILE_SIZE, 2.0, dark);
+            }
+        }
+    }
+}
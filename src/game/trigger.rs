@@ -0,0 +1,179 @@
+use crate::engine::core::Rect;
+use crate::game::level::TileType;
+use glam::Vec2;
+
+/// When a trigger fires relative to something being inside it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TriggerEvent {
+    /// The first time something steps in
+    Enter,
+    /// When the last thing inside leaves
+    Exit,
+    /// Repeatedly while something is inside, every `interval` seconds
+    Stay { interval: f32 },
+}
+
+/// What can set a trigger off
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TriggerSubject {
+    /// The pilot on foot
+    Player,
+    /// Any mech, piloted or not
+    Mech,
+    Either,
+}
+
+/// Extra requirements checked at the moment the event happens
+#[derive(Debug, Clone, PartialEq)]
+pub enum TriggerCondition {
+    /// No more than this many enemies left alive
+    EnemiesAtMost(usize),
+    /// At least this many collectibles picked up
+    ItemsCollected(usize),
+    /// The level has been running at least this many seconds
+    TimeElapsed(f32),
+    /// Another trigger (by name) has already fired
+    AfterTrigger(String),
+}
+
+/// What a trigger does when it fires
+#[derive(Debug, Clone, PartialEq)]
+pub enum TriggerAction {
+    /// Drop `count` enemies in around a position
+    SpawnWave { position: Vec2, count: usize, spread: f32 },
+    /// Banner text across the top of the screen
+    ShowMessage { text: String, duration: f32 },
+    /// Swap a block of tiles - Empty opens a door, Wall shuts one
+    SetTiles { x: usize, y: usize, width: usize, height: usize, tile_type: TileType },
+    /// Lock player input behind letterbox bars with a caption
+    StartCutscene { caption: String, duration: f32 },
+    /// Pull the camera out to keep these spots on screen with the player for a while
+    FrameCamera { points: Vec<Vec2>, duration: f32 },
+    /// Move the respawn point
+    SetCheckpoint(Vec2),
+    /// Finish the level's objective outright
+    CompleteObjective,
+}
+
+/// Level state a trigger's conditions are checked against
+pub struct TriggerContext<'a> {
+    pub enemy_count: usize,
+    pub items_collected: usize,
+    pub level_time: f32,
+    /// Names of triggers that have fired at least once
+    pub fired: &'a [String],
+}
+
+impl TriggerCondition {
+    pub fn is_met(&self, context: &TriggerContext) -> bool {
+        match self {
+            TriggerCondition::EnemiesAtMost(count) => context.enemy_count <= *count,
+            TriggerCondition::ItemsCollected(count) => context.items_collected >= *count,
+            TriggerCondition::TimeElapsed(seconds) => context.level_time >= *seconds,
+            TriggerCondition::AfterTrigger(name) => context.fired.iter().any(|fired| fired == name),
+        }
+    }
+}
+
+/// An invisible area of a level that runs actions when the player or a mech interacts with it
+#[derive(Debug, Clone)]
+pub struct TriggerVolume {
+    pub name: String,
+    pub rect: Rect,
+    pub event: TriggerEvent,
+    pub subject: TriggerSubject,
+    pub conditions: Vec<TriggerCondition>,
+    pub actions: Vec<TriggerAction>,
+    /// Only ever fire once per attempt at the level
+    pub once: bool,
+    /// Whether a matching subject was inside last frame
+    pub occupied: bool,
+    pub fire_count: u32,
+    stay_timer: f32,
+}
+
+impl TriggerVolume {
+    pub fn new(name: &str, rect: Rect, event: TriggerEvent) -> Self {
+        Self {
+            name: name.to_string(),
+            rect,
+            event,
+            subject: TriggerSubject::Either,
+            conditions: Vec::new(),
+            actions: Vec::new(),
+            once: true,
+            occupied: false,
+            fire_count: 0,
+            stay_timer: 0.0,
+        }
+    }
+
+    pub fn with_subject(mut self, subject: TriggerSubject) -> Self {
+        self.subject = subject;
+        self
+    }
+
+    pub fn with_condition(mut self, condition: TriggerCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    pub fn with_action(mut self, action: TriggerAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Keep firing every time the event happens instead of just the first
+    pub fn repeating(mut self) -> Self {
+        self.once = false;
+        self
+    }
+
+    /// A one-shot trigger that has already gone off
+    pub fn is_spent(&self) -> bool {
+        self.once && self.fire_count > 0
+    }
+
+    /// Forget everything that happened, for a level restart
+    pub fn reset(&mut self) {
+        self.occupied = false;
+        self.fire_count = 0;
+        self.stay_timer = 0.0;
+    }
+
+    /// Feed in whether a subject is inside this frame. True when the trigger's event happened
+    /// (conditions are checked separately).
+    pub fn update(&mut self, inside: bool, delta_time: f32) -> bool {
+        let was_inside = self.occupied;
+        self.occupied = inside;
+
+        match self.event {
+            TriggerEvent::Enter => inside && !was_inside,
+            TriggerEvent::Exit => was_inside && !inside,
+            TriggerEvent::Stay { interval } => {
+                if !inside {
+                    self.stay_timer = 0.0;
+                    return false;
+                }
+                // Fire straight away on entry, then once per interval
+                if !was_inside {
+                    self.stay_timer = interval;
+                    return true;
+                }
+                self.stay_timer -= delta_time;
+                if self.stay_timer <= 0.0 {
+                    self.stay_timer += interval;
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    /// Record a firing
+    pub fn fire(&mut self) -> Vec<TriggerAction> {
+        self.fire_count += 1;
+        self.actions.clone()
+    }
+}
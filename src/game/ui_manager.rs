@@ -1,6 +1,7 @@
 use crate::engine::ui::*;
 use crate::engine::core::Color;
 use crate::engine::rendering::Renderer;
+use crate::systems::mech::{EnergyAllocation, MechShield, PowerChannel, MAX_CHANNEL_PIPS};
 use crate::systems::player::Player;
 use crate::systems::weapons::Weapon;
 use crate::game::DayNightCycle;
@@ -12,6 +13,14 @@ pub struct UIManager {
     pub weapon_display: WeaponDisplay,
     pub clock: ClockWidget,
     pub debug_overlay: DebugOverlay,
+    mech_power: Option<(EnergyAllocation, MechShield)>,
+    stomp_readiness: Option<f32>,
+    /// Banner text and seconds left on screen
+    message: Option<(String, f32)>,
+    /// Cutscene caption and seconds left to play
+    cutscene: Option<(String, f32)>,
+    /// Lives and continues left
+    lives: (u32, u32),
     screen_size: Vec2,
 }
 
@@ -33,28 +42,38 @@ impl UIManager {
         
         let weapon_display = WeaponDisplay::new(Vec2::new(20.0, 90.0));
         
-        let clock_pos = Anchor::TopCenter.calculate_position(
-            screen_size,
-            Vec2::new(120.0, 40.0),
-            Vec2::new(0.0, 20.0),
-        );
-        let clock = ClockWidget::new(clock_pos);
-        
-        let debug_pos = Anchor::TopRight.calculate_position(
-            screen_size,
-            Vec2::new(250.0, 150.0),
-            Vec2::new(-20.0, 20.0),
-        );
-        let debug_overlay = DebugOverlay::new(debug_pos);
+        // Anchored widgets are placed by `resize`
+        let clock = ClockWidget::new(Vec2::ZERO);
+        let debug_overlay = DebugOverlay::new(Vec2::ZERO);
         
-        Self {
+        let mut ui = Self {
             health_bar,
             energy_bar,
             weapon_display,
             clock,
             debug_overlay,
+            mech_power: None,
+            stomp_readiness: None,
+            message: None,
+            cutscene: None,
+            lives: (0, 0),
             screen_size,
-        }
+        };
+        ui.resize(screen_size);
+        ui
+    }
+
+    /// Re-anchor the widgets to a new screen size
+    pub fn resize(&mut self, screen_size: Vec2) {
+        self.screen_size = screen_size;
+        let clock_pos = self.clock.anchor.calculate_position(screen_size, self.clock.size, Vec2::new(0.0, 20.0));
+        self.clock.set_position(clock_pos);
+        let debug_pos = self.debug_overlay.anchor.calculate_position(
+            screen_size,
+            self.debug_overlay.background.size,
+            Vec2::new(-20.0, 20.0),
+        );
+        self.debug_overlay.set_position(debug_pos);
     }
     
     pub fn update(
@@ -85,6 +104,15 @@ impl UIManager {
         
         self.debug_overlay.update_stats(delta_time, entity_count, player_pos, player_velocity);
         self.debug_overlay.update(delta_time);
+
+        for timed in [&mut self.message, &mut self.cutscene] {
+            if let Some((_, remaining)) = timed {
+                *remaining -= delta_time;
+                if *remaining <= 0.0 {
+                    *timed = None;
+                }
+            }
+        }
     }
     
     pub fn render(&self, renderer: &mut Renderer) {
@@ -107,6 +135,145 @@ impl UIManager {
         if self.debug_overlay.is_visible() {
             self.render_debug_overlay(renderer, &self.debug_overlay);
         }
+
+        if let Some((allocation, shield)) = &self.mech_power {
+            self.render_power_panel(renderer, allocation, shield);
+        }
+
+        if let Some(readiness) = self.stomp_readiness {
+            self.render_stomp_cooldown(renderer, readiness);
+        }
+
+        self.render_lives(renderer);
+
+        if let Some((caption, _)) = &self.cutscene {
+            self.render_cutscene(renderer, caption);
+        }
+
+        if let Some((text, remaining)) = &self.message {
+            self.render_message(renderer, text, *remaining);
+        }
+    }
+
+    pub fn set_lives(&mut self, lives: u32, continues: u32) {
+        self.lives = (lives, continues);
+    }
+
+    fn render_lives(&self, renderer: &mut Renderer) {
+        let size = Vec2::new(230.0, 26.0);
+        let pos = Anchor::BottomRight.calculate_position(self.screen_size, size, Vec2::new(-20.0, -20.0));
+        renderer.draw_ui_rect(pos, size, Color::new(20, 20, 30, 200));
+        renderer.draw_ui_rect_outline(pos, size, Color::new(100, 150, 200, 255), 2.0);
+        let label = format!("LIVES {}  CONTINUES {}", self.lives.0, self.lives.1);
+        renderer.draw_ui_text(pos + Vec2::new(10.0, 7.0), &label, Color::WHITE, 12);
+    }
+
+    /// Show a banner message for a few seconds, replacing any current one
+    pub fn show_message(&mut self, text: &str, duration: f32) {
+        self.message = Some((text.to_string(), duration));
+    }
+
+    /// Letterbox the screen with a caption; gameplay input should wait until it ends
+    pub fn start_cutscene(&mut self, caption: &str, duration: f32) {
+        self.cutscene = Some((caption.to_string(), duration));
+    }
+
+    pub fn is_cutscene_playing(&self) -> bool {
+        self.cutscene.is_some()
+    }
+
+    fn render_message(&self, renderer: &mut Renderer, text: &str, remaining: f32) {
+        // Sits under the clock, fading out over its last half second
+        let size = Vec2::new(text.len() as f32 * 12.0 + 40.0, 36.0);
+        let pos = Anchor::TopCenter.calculate_position(self.screen_size, size, Vec2::new(0.0, 80.0));
+        let alpha = (remaining / 0.5).clamp(0.0, 1.0);
+
+        renderer.draw_ui_rect(pos, size, Color::new(20, 20, 30, (200.0 * alpha) as u8));
+        renderer.draw_ui_rect_outline(pos, size, Color::new(255, 200, 80, (255.0 * alpha) as u8), 2.0);
+        renderer.draw_ui_text(pos + Vec2::new(20.0, 11.0), text, Color::new(255, 255, 255, (255.0 * alpha) as u8), 14);
+    }
+
+    fn render_cutscene(&self, renderer: &mut Renderer, caption: &str) {
+        let bar_height = self.screen_size.y * 0.12;
+        renderer.draw_ui_rect(Vec2::ZERO, Vec2::new(self.screen_size.x, bar_height), Color::BLACK);
+        renderer.draw_ui_rect(
+            Vec2::new(0.0, self.screen_size.y - bar_height),
+            Vec2::new(self.screen_size.x, bar_height),
+            Color::BLACK,
+        );
+
+        let caption_width = caption.len() as f32 * 12.0;
+        let caption_pos = Vec2::new(
+            (self.screen_size.x - caption_width) / 2.0,
+            self.screen_size.y - bar_height / 2.0 - 7.0,
+        );
+        renderer.draw_ui_text(caption_pos, caption, Color::WHITE, 14);
+    }
+
+    /// Stomp cooldown of the piloted mech (None while on foot)
+    pub fn set_stomp_readiness(&mut self, readiness: Option<f32>) {
+        self.stomp_readiness = readiness;
+    }
+
+    fn render_stomp_cooldown(&self, renderer: &mut Renderer, readiness: f32) {
+        // Sits just above the power panel
+        let size = Vec2::new(220.0, 26.0);
+        let pos = Anchor::BottomLeft.calculate_position(self.screen_size, size, Vec2::new(20.0, -150.0));
+        let ready = readiness >= 1.0;
+        let color = if ready { Color::new(255, 120, 40, 255) } else { Color::new(120, 90, 70, 255) };
+
+        renderer.draw_ui_rect(pos, size, Color::new(20, 20, 30, 200));
+        renderer.draw_ui_rect(pos, Vec2::new(size.x * readiness.clamp(0.0, 1.0), size.y), Color::new(color.r, color.g, color.b, 90));
+        renderer.draw_ui_rect_outline(pos, size, color, 2.0);
+
+        let label = if ready { "STOMP READY" } else { "STOMP" };
+        renderer.draw_ui_text(pos + Vec2::new(10.0, 7.0), label, Color::WHITE, 12);
+    }
+
+    /// Power split and shield status of the piloted mech (None while on foot)
+    pub fn set_mech_power(&mut self, power: Option<(EnergyAllocation, MechShield)>) {
+        self.mech_power = power;
+    }
+
+    fn render_power_panel(&self, renderer: &mut Renderer, allocation: &EnergyAllocation, shield: &MechShield) {
+        let panel_size = Vec2::new(220.0, 120.0);
+        let panel_pos = Anchor::BottomLeft.calculate_position(self.screen_size, panel_size, Vec2::new(20.0, -20.0));
+
+        renderer.draw_ui_rect(panel_pos, panel_size, Color::new(20, 20, 30, 200));
+        renderer.draw_ui_rect_outline(panel_pos, panel_size, Color::new(100, 150, 200, 255), 2.0);
+        renderer.draw_ui_text(panel_pos + Vec2::new(10.0, 8.0), "POWER", Color::WHITE, 12);
+
+        // One row of pips per channel, with the hotkey that feeds it
+        for (row, channel) in PowerChannel::ALL.iter().enumerate() {
+            let row_pos = panel_pos + Vec2::new(10.0, 28.0 + row as f32 * 20.0);
+            let label = format!("{} {}", row + 1, channel.name());
+            renderer.draw_ui_text(row_pos, &label, Color::new(200, 200, 200, 255), 12);
+
+            for pip in 0..MAX_CHANNEL_PIPS {
+                let pip_pos = row_pos + Vec2::new(70.0 + pip as f32 * 26.0, 0.0);
+                if pip < allocation.pips(*channel) {
+                    renderer.draw_ui_rect(pip_pos, Vec2::new(22.0, 12.0), channel.color());
+                } else {
+                    renderer.draw_ui_rect_outline(pip_pos, Vec2::new(22.0, 12.0), Color::new(80, 80, 100, 255), 1.0);
+                }
+            }
+        }
+
+        // Shield state, with a cooldown bar while the emitter recovers
+        let status_pos = panel_pos + Vec2::new(10.0, 94.0);
+        if shield.is_overheated() {
+            renderer.draw_ui_text(status_pos, "SHIELD OVERHEAT", Color::new(255, 80, 60, 255), 12);
+            let remaining = shield.overheat_timer / shield.overheat_duration;
+            renderer.draw_ui_rect(
+                status_pos + Vec2::new(0.0, 14.0),
+                Vec2::new((panel_size.x - 20.0) * remaining, 3.0),
+                Color::new(255, 80, 60, 255),
+            );
+        } else if shield.is_raised {
+            renderer.draw_ui_text(status_pos, "SHIELD UP", PowerChannel::Shields.color(), 12);
+        } else {
+            renderer.draw_ui_text(status_pos, "SHIELD READY", Color::new(150, 150, 170, 255), 12);
+        }
     }
     
     fn render_progress_bar(&self, renderer: &mut Renderer, bar: &ProgressBar) {
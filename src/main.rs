@@ -1,6 +1,4 @@
-mod engine;
-mod game;
-mod systems;
+use legends_of_legend::{engine, game, systems};
 
 use anyhow::Result;
 use glam::Vec2;
@@ -8,36 +6,55 @@ use log::info;
 use sdl2::keyboard::Keycode;
 
 use engine::core::{Color, Transform};
-use engine::physics::{Collider, RigidBody};
-use engine::rendering::Sprite;
+use engine::physics::{character_physics_system, entity_collision_system, CharacterBody, Collider, CollisionFilter, RigidBody, SpatialHash};
+use engine::rendering::{animation_system, Camera, CameraController, DrawCommand, LightMap, ParallaxConfig, PointLight, RenderLayer, RenderQueue, Renderer, Sprite, SPRITE_MANIFEST_PATH};
 use engine::physics::RigidBody as RB;
-use engine::ui::Minimap;
+use engine::platform::InputState;
+use engine::ui::{Anchor, Minimap, MinimapMarkers, UIElement};
 use game::states::{GameState, PlayState};
-use game::{DayNightCycle, Level, LevelManager, UIManager, WinProgress, TILE_SIZE};
+use game::level::TileType;
+use game::{submit_city, submit_city_lights, DayNightCycle, TileChunkCache, HangarAction, HangarScreen, Level, LevelManager, UIManager, WinProgress, TILE_SIZE};
+use game::{import_legacy_loadout, MainMenu, MenuAction, SaveGame, SaveStore, Settings};
 use systems::player::{player_movement_system, player_shooting_system, Player, PlayerController};
-use systems::enemy::{Enemy, EnemyController, enemy_ai_system, enemy_physics_system};
+use systems::enemy::{Enemy, EnemyController, enemy_ai_system, enemy_contact_damage_system};
 use systems::projectile::{Projectile, ProjectileOwner, projectile_system};
-use systems::particles::{update_particles};
+use systems::particles::{update_particles, ParticleSystem};
 use systems::enemy_spawner::EnemySpawner;
-use systems::win_condition_system::{check_win_conditions, check_collectibles, spawn_collectibles, spawn_goal_marker};
-use systems::mech::{Mech, MechController, MechWeaponInventory, mech_movement_system, spawn_mech, enter_mech, exit_mech, find_nearest_mech};
+use systems::trigger_system::{apply_trigger_action, draw_trigger_volumes, trigger_system};
+use systems::respawn_system::{checkpoint_system, invulnerability_system, player_died, recover_piloted_mech, reset_enemies_near, respawn_player, spawn_checkpoints};
+use systems::respawn_system::{CheckpointMarker, DeathOutcome, Lives, ENEMY_RESET_RADIUS};
+use systems::win_condition_system::{check_win_conditions, check_collectibles, respawn_collectibles, spawn_collectibles, spawn_goal_marker, CollectibleMarker};
+use systems::mech::{Mech, MechController, MechWeaponInventory, mech_movement_system, find_nearest_mech};
+use systems::mech::{MechLoadout, refit_mech, spawn_mech_with_loadout};
+use systems::mech::{MechTransition, TransitionEvent, begin_embark, begin_disembark, mech_transition_system, ON_FOOT_ZOOM, MECH_ZOOM};
+use systems::mech::{CompanionCommand, MechCompanion, companion_markers, find_enemy_near, issue_companion_command, mech_companion_system, COMPANION_GUARD_RADIUS};
+use systems::mech::{EnergyAllocation, MechShield, MechShot, draw_mech_shields, mech_power_system, mech_shooting_system};
+use systems::mech::{draw_stomp_telegraphs, resolve_stomp, stomp_landing_system};
+use systems::animation_states::{mech_animation_system, mech_animator, player_animation_system, player_animator, HitReaction, FOOTSTEP};
+use systems::lighting_system::{collect_lights, light_flash_system, mech_headlight_system, spawn_light_flash};
+
+/// Screen size the UI is laid out for, and the virtual resolution when pixel scaling is on
+const DESIGN_RESOLUTION: (u32, u32) = (1280, 720);
+/// Minimap inset from the top-right corner of the screen
+const MINIMAP_OFFSET: Vec2 = Vec2::new(-30.0, 20.0);
+/// Frames between saves while recording a capture sequence (every third frame is 20 fps at 60 Hz)
+const CAPTURE_EVERY_NTH_FRAME: u32 = 3;
 
 fn main() -> Result<()> {
     env_logger::init();
     info!("Starting Legends of Legend...");
 
-    let mut engine = engine::Engine::new("Legends of Legend", 1280, 720)?;
+    let mut engine = engine::Engine::new("Legends of Legend", DESIGN_RESOLUTION.0, DESIGN_RESOLUTION.1)?;
 
     let mut level_manager = LevelManager::new();
+    let mut camera_controller = CameraController::new();
+    let mut manual_zoom: Option<f32> = None;
     let spawn_pos = {
         let level = level_manager.get_current_level();
-        let spawn = level.spawn_point;
-        // Position camera to show ground at bottom of screen
-        // Ground is at (height - 3) * TILE_SIZE, we want it at bottom of 720px viewport
-        let ground_y = (level.height - 3) as f32 * TILE_SIZE;
-        let camera_y = ground_y - 600.0 / 2.0 + 500.0;  // Position camera so ground is near bottom
-        engine.renderer.camera.position = Vec2::new(spawn.x, camera_y);
-        spawn
+        // The level bounds keep the ground near the bottom of the screen
+        camera_controller.set_bounds(level.camera_bounds());
+        camera_controller.snap_to(&mut engine.renderer.camera, level.spawn_point);
+        level.spawn_point
     };
 
     let player_entity = engine.world.spawn((
@@ -48,18 +65,12 @@ fn main() -> Result<()> {
         Collider::Box {
             size: Vec2::new(24.0, 40.0),
         },
+        CharacterBody::new(Vec2::new(24.0, 40.0), 800.0),
+        CollisionFilter::player(),
         PlayerController::new(),
     ));
 
-    // Define spawn points for enemies (off-screen left and right)
-    let spawn_points = vec![
-        Vec2::new(spawn_pos.x - 800.0, spawn_pos.y),  // Far left
-        Vec2::new(spawn_pos.x + 800.0, spawn_pos.y),  // Far right
-        Vec2::new(spawn_pos.x - 1000.0, spawn_pos.y - 100.0), // Upper left
-        Vec2::new(spawn_pos.x + 1000.0, spawn_pos.y - 100.0), // Upper right
-    ];
-    
-    let mut enemy_spawner = EnemySpawner::new(spawn_points);
+    let mut enemy_spawner = EnemySpawner::new(enemy_spawn_points(spawn_pos));
     
     // Spawn a few initial enemies
     for i in 0..3 {
@@ -72,6 +83,8 @@ fn main() -> Result<()> {
             Collider::Box {
                 size: Vec2::new(32.0, 48.0),
             },
+            CharacterBody::new(Vec2::new(32.0, 48.0), 800.0),
+            CollisionFilter::enemy(),
             EnemyController::new(),
         ));
     }
@@ -87,27 +100,172 @@ fn main() -> Result<()> {
     let mut current_pilot_entity = Some(player_entity);
     let mut current_mech_entity: Option<hecs::Entity> = None;
     
-    // Spawn a mech near the player for testing
-    let mech_entity = spawn_mech(&mut engine.world, Vec2::new(spawn_pos.x + 200.0, spawn_pos.y));
+    // Spawn a mech near the player; it's refitted with the saved loadout once a slot is picked
+    let mech_loadout = MechLoadout::default();
+    let mech_entity = spawn_mech_with_loadout(&mut engine.world, Vec2::new(spawn_pos.x + 200.0, spawn_pos.y), &mech_loadout);
     info!("Spawned test mech: {:?}", mech_entity);
+
+    // Swap the placeholder rectangles for sprite sheets where the art exists
+    match engine.renderer.assets.load_manifest(SPRITE_MANIFEST_PATH) {
+        Ok(0) => info!("No sprite sheets found, drawing placeholders"),
+        Ok(count) => info!("Loaded {} sprite sheets", count),
+        Err(e) => log::warn!("Sprite manifest unreadable, drawing placeholders ({:#})", e),
+    }
+    for (entity, name) in [(player_entity, "player"), (mech_entity, "mech")] {
+        if let (Some(sheet), Ok(mut sprite)) = (engine.renderer.assets.sheet(name), engine.world.get::<&mut Sprite>(entity)) {
+            sheet.apply(&mut sprite);
+        }
+    }
+    let player_sheet = engine.renderer.assets.sheet("player").cloned();
+    let mech_sheet = engine.renderer.assets.sheet("mech").cloned();
+    let _ = engine.world.insert(player_entity, (player_animator(player_sheet), HitReaction { last_health: 100.0 }));
+    let _ = engine.world.insert_one(mech_entity, mech_animator(mech_sheet));
+    let screen_size = engine.renderer.screen_size();
+    let mut hangar = HangarScreen::new(screen_size.x, screen_size.y, mech_loadout);
     let mut day_night_cycle = DayNightCycle::new();
-    let mut ui_manager = UIManager::new(1280.0, 720.0);
+    let mut ui_manager = UIManager::new(screen_size.x, screen_size.y);
     
     // Create minimap in top-right corner
-    let minimap_pos = Vec2::new(1280.0 - 160.0 - 20.0, 20.0);  // 20px margin from edges
     let minimap_size = Vec2::new(150.0, 150.0);
-    let mut minimap = Minimap::new(minimap_pos, minimap_size);
+    let mut minimap = Minimap::new(Vec2::ZERO, minimap_size);
+    minimap.anchor_to(Anchor::TopRight, screen_size, MINIMAP_OFFSET);
+    let mut broadphase = SpatialHash::default();
+    let mut lives = Lives::new();
+    spawn_collectibles(&mut engine.world, &level_manager);
+    spawn_checkpoints(&mut engine.world, &level_manager);
+
+    // Campaign saves, starting on the title screen with the last played slot picked
+    let save_store = SaveStore::open_default();
+    info!("Save directory: {}", save_store.dir().display());
+    let mut active_slot = 0;
+    let mut settings = Settings::default();
+    let mut main_menu = MainMenu::new(screen_size.x, screen_size.y);
+    let mut laid_out_for = screen_size;
+    main_menu.open(save_store.summaries(), save_store.latest_slot());
+    let mut render_queue = RenderQueue::new();
+    let mut light_map = LightMap::new();
+    let mut tile_chunks = TileChunkCache::new();
 
     engine.run(move |engine, delta_time| {
-        // Update day/night cycle
-        day_night_cycle.update(delta_time);
         // Clear with black instead of sky blue (sky will be drawn as gradient)
         engine.renderer.clear(Color::new(0, 0, 0, 255));
 
+        // Re-anchor screen-space UI whenever the window or virtual resolution changes size
+        let screen_size = engine.renderer.screen_size();
+        if screen_size != laid_out_for {
+            laid_out_for = screen_size;
+            ui_manager.resize(screen_size);
+            hangar.resize(screen_size);
+            main_menu.resize(screen_size);
+            minimap.anchor_to(Anchor::TopRight, screen_size, MINIMAP_OFFSET);
+        }
+
+        // Cached tile chunks live in render targets, which the driver may have wiped
+        if engine.platform.render_targets_reset {
+            tile_chunks.invalidate_all();
+        }
+
+        // F12 saves a screenshot, F11 starts or stops saving every few frames for GIFs
+        if engine.platform.input.is_key_pressed(Keycode::F12) {
+            let path = engine.renderer.capture.request_screenshot();
+            info!("Saving screenshot to {}", path.display());
+        }
+        if engine.platform.input.is_key_pressed(Keycode::F11) {
+            if let Some(saved) = engine.renderer.capture.stop_sequence() {
+                info!("Stopped recording after {} frames", saved);
+            } else {
+                let dir = engine.renderer.capture.start_sequence(CAPTURE_EVERY_NTH_FRAME);
+                info!("Recording every {} frames to {}", CAPTURE_EVERY_NTH_FRAME, dir.display());
+            }
+        }
+
+        // Title screen: continue a save slot or start a new campaign in one
+        if main_menu.is_open {
+            let chosen = match main_menu.handle_input(&engine.platform.input) {
+                MenuAction::Continue { slot, level } => match save_store.load(slot) {
+                    Ok(mut save) => {
+                        save.current_level = level;
+                        Some((slot, save))
+                    },
+                    Err(e) => {
+                        log::warn!("Failed to load save slot {}: {:#}", slot + 1, e);
+                        None
+                    },
+                },
+                MenuAction::NewGame { slot } => {
+                    Some((slot, SaveGame::new(import_legacy_loadout().unwrap_or_default())))
+                },
+                MenuAction::None => None,
+            };
+
+            if let Some((slot, save)) = chosen {
+                main_menu.close();
+                active_slot = slot;
+                level_manager.restore_progress(save.current_level, save.unlocked_level);
+                lives = save.lives();
+                settings = save.settings.clone();
+                apply_pixel_scaling(&mut engine.renderer, settings.pixel_scaling);
+                ui_manager.debug_overlay.visible = settings.show_debug_overlay;
+                hangar.loadout = save.loadout.clone();
+                refit_mech(&mut engine.world, mech_entity, &hangar.loadout);
+                enemy_spawner = start_level(&mut engine.world, &mut engine.renderer.camera, &mut camera_controller, &level_manager, &mut tile_chunks, player_entity, mech_entity);
+                save_progress(&save_store, active_slot, &level_manager, &lives, &hangar.loadout, &settings);
+            }
+            main_menu.render(&mut engine.renderer);
+            return;
+        }
+
+        // Hangar screen pauses the game until the player launches
+        if hangar.is_open {
+            if hangar.handle_input(&engine.platform.input) == HangarAction::Launch {
+                hangar.close();
+                refit_mech(&mut engine.world, mech_entity, &hangar.loadout);
+
+                if level_manager.is_level_complete() {
+                    if !level_manager.next_level() {
+                        info!("All levels complete!");
+                        level_manager.restart_level();
+                    }
+                    enemy_spawner = start_level(&mut engine.world, &mut engine.renderer.camera, &mut camera_controller, &level_manager, &mut tile_chunks, player_entity, mech_entity);
+                }
+                save_progress(&save_store, active_slot, &level_manager, &lives, &hangar.loadout, &settings);
+            }
+            hangar.render(&mut engine.renderer);
+            return;
+        }
+
+        // Game over holds until the player starts again from the first level
+        if matches!(game_state, GameState::GameOver) {
+            let center = screen_size / 2.0;
+            engine.renderer.draw_ui_text(center + Vec2::new(-120.0, -60.0), "GAME OVER", Color::new(255, 60, 60, 255), 28);
+            engine.renderer.draw_ui_text(center + Vec2::new(-170.0, 0.0), "PRESS ENTER TO TRY AGAIN", Color::WHITE, 14);
+            if engine.platform.input.is_key_pressed(Keycode::Return) {
+                enemy_spawner = start_level(&mut engine.world, &mut engine.renderer.camera, &mut camera_controller, &level_manager, &mut tile_chunks, player_entity, mech_entity);
+                game_state = GameState::default();
+            }
+            return;
+        }
+
+        // Update day/night cycle
+        day_night_cycle.update(delta_time);
+
+        // Open the hangar between levels once the objective is complete
+        level_manager.update(delta_time);
+        let (level_complete, _) = check_win_conditions(&mut engine.world, &mut level_manager);
+        if level_complete && current_play_mode != PlayState::Transition {
+            info!("Level complete - returning to hangar");
+            hangar.open();
+            return;
+        }
+
         let level = level_manager.get_current_level();
+
+        // Cutscenes hold the controls, so gameplay systems see nothing pressed
+        let idle_input = InputState::default();
+        let gameplay_input = if ui_manager.is_cutscene_playing() { &idle_input } else { &engine.platform.input };
         
         // Handle mech entry/exit with E key
-        if engine.platform.input.is_key_pressed(Keycode::E) {
+        if gameplay_input.is_key_pressed(Keycode::E) {
             match current_play_mode {
                 PlayState::OnFoot => {
                     // Try to enter a nearby mech
@@ -120,9 +278,9 @@ fn main() -> Result<()> {
                         
                         if let Some(pos) = pilot_pos {
                             if let Some(mech) = find_nearest_mech(&engine.world, pos, 100.0) {
-                                let result = enter_mech(&mut engine.world, pilot_entity, mech);
+                                let result = begin_embark(&mut engine.world, pilot_entity, mech);
                                 if result.success {
-                                    current_play_mode = PlayState::InMech;
+                                    current_play_mode = PlayState::Transition;
                                     current_mech_entity = Some(mech);
                                     info!("{}", result.message);
                                 }
@@ -131,12 +289,11 @@ fn main() -> Result<()> {
                     }
                 },
                 PlayState::InMech => {
-                    // Exit the current mech
+                    // Start climbing out of the current mech
                     if let Some(mech) = current_mech_entity {
-                        let result = exit_mech(&mut engine.world, mech);
+                        let result = begin_disembark(&mut engine.world, mech);
                         if result.success {
-                            current_play_mode = PlayState::OnFoot;
-                            current_mech_entity = None;
+                            current_play_mode = PlayState::Transition;
                             info!("{}", result.message);
                         }
                     }
@@ -145,20 +302,72 @@ fn main() -> Result<()> {
             }
         }
         
+        // Advance embark/disembark sequences (input stays locked until they finish)
+        for event in mech_transition_system(&mut engine.world, delta_time) {
+            match event {
+                TransitionEvent::Embarked { mech } => {
+                    current_play_mode = PlayState::InMech;
+                    current_mech_entity = Some(mech);
+                    info!("Entered Titan mech");
+                },
+                TransitionEvent::Disembarked { mech } => {
+                    current_play_mode = PlayState::OnFoot;
+                    current_mech_entity = None;
+                    info!("Exited Titan mech");
+
+                    // A freshly parked mech holds where it was left until told otherwise
+                    let parked_at = engine.world.get::<&Transform>(mech).map(|t| t.position).ok();
+                    if let Some(position) = parked_at {
+                        issue_companion_command(&mut engine.world, mech, CompanionCommand::HoldPosition(position));
+                    }
+                },
+                TransitionEvent::Interrupted { mech, pilot_inside } => {
+                    if pilot_inside {
+                        current_play_mode = PlayState::InMech;
+                        current_mech_entity = Some(mech);
+                    } else {
+                        current_play_mode = PlayState::OnFoot;
+                        current_mech_entity = None;
+                    }
+                },
+            }
+        }
+        
+        // Companion orders for the parked mech: Z follow, X hold, C guard cursor area, V attack enemy under cursor
+        if current_play_mode == PlayState::OnFoot {
+            let input = gameplay_input;
+            let cursor_world = engine.renderer.camera.screen_to_world(input.mouse_position());
+            let command = if input.is_key_pressed(Keycode::Z) {
+                Some(CompanionCommand::Follow)
+            } else if input.is_key_pressed(Keycode::X) {
+                engine.world.get::<&Transform>(mech_entity).ok().map(|t| CompanionCommand::HoldPosition(t.position))
+            } else if input.is_key_pressed(Keycode::C) {
+                Some(CompanionCommand::Guard { center: cursor_world, radius: COMPANION_GUARD_RADIUS })
+            } else if input.is_key_pressed(Keycode::V) {
+                find_enemy_near(&engine.world, cursor_world, 150.0).map(CompanionCommand::Attack)
+            } else {
+                None
+            };
+
+            if let Some(command) = command {
+                issue_companion_command(&mut engine.world, mech_entity, command);
+            }
+        }
+
         // Run appropriate movement system based on current mode
+        let mut crushed_tiles = Vec::new();
         match current_play_mode {
             PlayState::OnFoot => {
                 player_movement_system(
                     &mut engine.world,
-                    &engine.platform.input,
-                    level,
+                    gameplay_input,
                     delta_time,
                 );
             },
             PlayState::InMech => {
-                mech_movement_system(
+                crushed_tiles = mech_movement_system(
                     &mut engine.world,
-                    &engine.platform.input,
+                    gameplay_input,
                     level,
                     delta_time,
                 );
@@ -166,22 +375,36 @@ fn main() -> Result<()> {
             _ => {}
         }
 
+        // Power split, shields and energy regeneration
+        mech_power_system(
+            &mut engine.world,
+            gameplay_input,
+            &engine.renderer.camera,
+            delta_time,
+        );
+
         // Update enemy spawner
         enemy_spawner.update(&mut engine.world, delta_time);
         
-        // Update enemy physics
-        enemy_physics_system(&mut engine.world, level, delta_time);
-
         // Player/Mech shooting system
-        let player_projectiles = if current_play_mode == PlayState::OnFoot {
-            player_shooting_system(
+        let (player_projectiles, pilot_shots) = if current_play_mode == PlayState::OnFoot {
+            let projectiles = player_shooting_system(
                 &mut engine.world,
-                &engine.platform.input,
+                gameplay_input,
                 &engine.renderer.camera,
                 delta_time,
-            )
+            );
+            (projectiles, Vec::new())
+        } else if current_play_mode == PlayState::InMech {
+            let shots = mech_shooting_system(
+                &mut engine.world,
+                gameplay_input,
+                &engine.renderer.camera,
+                delta_time,
+            );
+            (Vec::new(), shots)
         } else {
-            Vec::new() // TODO: Implement mech shooting system
+            (Vec::new(), Vec::new())
         };
         
         for spawn_data in player_projectiles {
@@ -196,9 +419,22 @@ fn main() -> Result<()> {
                 Sprite::new(projectile.size, projectile.color),
                 body,
                 Collider::Circle { radius: projectile.size.x / 2.0 },
+                CollisionFilter::friendly_projectile(),
             ));
         }
 
+        // Unmanned mechs act on their own orders
+        let pilot_position = if current_play_mode == PlayState::OnFoot {
+            engine.world.get::<&Transform>(player_entity).map(|t| t.position).ok()
+        } else {
+            None
+        };
+        let companion_shots = mech_companion_system(&mut engine.world, level, pilot_position, delta_time);
+
+        for shot in pilot_shots.into_iter().chain(companion_shots) {
+            spawn_mech_shot(&mut engine.world, shot);
+        }
+
         // Run enemy AI and spawn projectiles
         let enemy_projectiles = enemy_ai_system(&mut engine.world, delta_time);
         
@@ -212,58 +448,179 @@ fn main() -> Result<()> {
                 Sprite::new(Vec2::new(8.0, 8.0), Color::new(255, 200, 0, 255)), // Yellow/orange projectile
                 body,
                 Collider::Circle { radius: 4.0 },
+                CollisionFilter::hostile_projectile(),
             ));
         }
 
+        // Resolve player, enemy and mech movement against the level
+        character_physics_system(&mut engine.world, level, delta_time);
+
+        // Index everything with a collider at its resolved position for hit queries
+        broadphase.rebuild(&engine.world);
+
+        // Solid bodies shove each other apart, rushers hurt whatever they run into
+        let body_contacts = entity_collision_system(&mut engine.world, level, &broadphase);
+        for particle in enemy_contact_damage_system(&mut engine.world, &body_contacts) {
+            systems::particles::spawn_particle(&mut engine.world, particle);
+        }
+
+        // Stomp shockwaves hit enemies, shake the camera and break tiles
+        for impact in stomp_landing_system(&mut engine.world) {
+            let outcome = resolve_stomp(&mut engine.world, level, &broadphase, &impact);
+            for particle in outcome.particles {
+                systems::particles::spawn_particle(&mut engine.world, particle);
+            }
+            if settings.screen_shake {
+                camera_controller.add_trauma(outcome.trauma);
+            }
+            crushed_tiles.extend(outcome.crushed_tiles);
+        }
+
+        // Tiles flattened by heavy mechs turn into rubble
+        if !crushed_tiles.is_empty() {
+            let level = level_manager.get_current_level_mut();
+            for (x, y) in crushed_tiles {
+                let tile_color = level.tiles[y][x].color;
+                let center = Vec2::new((x as f32 + 0.5) * TILE_SIZE, (y as f32 + 0.5) * TILE_SIZE);
+                for particle in ParticleSystem::create_debris_particles(center, tile_color, 6) {
+                    systems::particles::spawn_particle(&mut engine.world, particle);
+                }
+                level.set_tile(x, y, TileType::Empty);
+            }
+        }
+
+        // Power cores picked up by the player or their mech
+        for entity in check_collectibles(&engine.world, &mut level_manager, &broadphase) {
+            let _ = engine.world.despawn(entity);
+        }
+
+        // Level scripting: doors, ambushes, checkpoints and messages
+        for action in trigger_system(&engine.world, &mut level_manager, &broadphase, delta_time) {
+            apply_trigger_action(&mut engine.world, &mut level_manager, &enemy_spawner, &mut ui_manager, &mut camera_controller, action);
+        }
+        if checkpoint_system(&mut engine.world, &mut level_manager, &broadphase) {
+            ui_manager.show_message("CHECKPOINT", 1.5);
+        }
+
+        // Out of health: back to the last checkpoint, a continue restarts the level, then game over
+        if player_died(&engine.world, player_entity) {
+            // A wrecked mech throws its pilot out, so every outcome carries on on foot
+            if recover_piloted_mech(&mut engine.world, player_entity, level_manager.get_respawn_point()).is_some() {
+                current_play_mode = PlayState::OnFoot;
+                current_mech_entity = None;
+            }
+            match lives.lose_life() {
+                DeathOutcome::Respawn => {
+                    if level_manager.on_player_death() {
+                        respawn_collectibles(&mut engine.world, &level_manager);
+                    }
+                    let respawn_point = level_manager.get_respawn_point();
+                    reset_enemies_near(&mut engine.world, respawn_point, ENEMY_RESET_RADIUS);
+                    respawn_player(&mut engine.world, player_entity, respawn_point);
+                    ui_manager.show_message(&format!("LIVES LEFT {}", lives.lives), 2.0);
+                },
+                DeathOutcome::Continue => {
+                    level_manager.restart_level();
+                    enemy_spawner = start_level(&mut engine.world, &mut engine.renderer.camera, &mut camera_controller, &level_manager, &mut tile_chunks, player_entity, mech_entity);
+                    respawn_player(&mut engine.world, player_entity, level_manager.get_respawn_point());
+                    ui_manager.show_message(&format!("CONTINUES LEFT {}", lives.continues), 2.5);
+                },
+                DeathOutcome::GameOver => {
+                    // The campaign starts over, though unlocked levels stay unlocked
+                    info!("Game over");
+                    game_state = GameState::GameOver;
+                    level_manager.go_to_level(0);
+                    lives = Lives::new();
+                },
+            }
+            save_progress(&save_store, active_slot, &level_manager, &lives, &hangar.loadout, &settings);
+        }
+        // Tile edits since last frame (doors, rubble) and level changes invalidate cached tile chunks
+        tile_chunks.sync(level_manager.get_current_level_mut());
+        let level = level_manager.get_current_level();
+
         // Update projectiles with physics and check collisions
-        let (expired_projectiles, new_particles) = projectile_system(&mut engine.world, level, delta_time);
+        let projectiles = projectile_system(&mut engine.world, level, &broadphase, delta_time);
+        let expired_projectiles = projectiles.expired;
         
         // Spawn new particles from projectile impacts
-        for particle in new_particles {
+        for particle in projectiles.particles {
             systems::particles::spawn_particle(&mut engine.world, particle);
         }
+
+        // Explosions rattle the camera, less the further away they go off
+        if settings.screen_shake {
+            for (position, _radius) in projectiles.explosions {
+                let distance = position.distance(engine.renderer.camera.position);
+                camera_controller.add_trauma(0.5 * (1.0 - distance / 1200.0).max(0.0));
+            }
+        }
         
         // Update particles
         let expired_particles = update_particles(&mut engine.world, delta_time);
+        invulnerability_system(&mut engine.world, delta_time);
+
+        // Sprite animation; footsteps kick up dust and heavy mech steps rattle the camera
+        player_animation_system(&mut engine.world);
+        mech_animation_system(&mut engine.world);
+        for event in animation_system(&mut engine.world, delta_time) {
+            if event.name != FOOTSTEP {
+                continue;
+            }
+            let foot = engine.world.query_one_mut::<(&Transform, &Sprite)>(event.entity)
+                .map(|(transform, sprite)| transform.position + Vec2::new(0.0, sprite.size.y / 2.0));
+            let Ok(foot) = foot else { continue };
+            let is_mech = engine.world.satisfies::<&Mech>(event.entity).unwrap_or(false);
+            let (radius, count) = if is_mech { (40.0, 8) } else { (12.0, 3) };
+            for particle in ParticleSystem::create_shockwave_particles(foot, radius, count) {
+                systems::particles::spawn_particle(&mut engine.world, particle);
+            }
+            if is_mech && Some(event.entity) == current_mech_entity && settings.screen_shake {
+                camera_controller.add_trauma(0.3);
+            }
+        }
         
-        // Remove expired projectiles and particles
-        for entity in expired_projectiles.into_iter().chain(expired_particles) {
+        // Headlights follow their mech and flashes fade out
+        mech_headlight_system(&mut engine.world);
+        let expired_flashes = light_flash_system(&mut engine.world, delta_time);
+
+        // Remove expired projectiles, particles and light flashes
+        for entity in expired_projectiles.into_iter().chain(expired_particles).chain(expired_flashes) {
             let _ = engine.world.despawn(entity);
         }
 
         // Camera zoom controls (moved to F keys to free up number keys for weapons)
-        // Auto-adjust zoom based on play mode
-        let target_zoom = match current_play_mode {
-            PlayState::OnFoot => 1.0,
-            PlayState::InMech => 0.5,
-            _ => engine.renderer.camera.zoom,
-        };
-        
-        // Smoothly transition camera zoom
-        let zoom_speed = 3.0;
-        let current_zoom = engine.renderer.camera.zoom;
-        let new_zoom = current_zoom + (target_zoom - current_zoom) * zoom_speed * delta_time;
-        engine.renderer.camera.set_zoom(new_zoom);
-        
-        // Manual zoom override
+        // Manual zoom overrides the play mode's zoom until F1 hands it back
         if engine.platform.input.is_key_pressed(Keycode::F1) {
-            engine.renderer.camera.set_zoom(1.0);  // Normal view
-            info!("On-foot view");
+            manual_zoom = None;  // Normal view for the play mode
+            info!("Automatic view");
         }
         if engine.platform.input.is_key_pressed(Keycode::F2) {
-            engine.renderer.camera.set_zoom(0.5);  // Zoomed out for wider view
+            manual_zoom = Some(0.5);  // Zoomed out for wider view
             info!("Wide view");
         }
         if engine.platform.input.is_key_pressed(Keycode::F4) {
-            engine.renderer.camera.set_zoom(0.25);  // Very wide view
+            manual_zoom = Some(0.25);  // Very wide view
             info!("Mech view");
         }
 
+        // Auto-adjust zoom based on play mode; the controller eases towards it
+        let transition_zoom = current_mech_entity
+            .and_then(|mech| engine.world.get::<&MechTransition>(mech).ok().map(|t| t.camera_zoom()));
+        let mode_zoom = match current_play_mode {
+            PlayState::OnFoot => ON_FOOT_ZOOM,
+            PlayState::InMech => MECH_ZOOM,
+            PlayState::Transition => transition_zoom.unwrap_or(camera_controller.target_zoom()),
+            _ => camera_controller.target_zoom(),
+        };
+        camera_controller.set_target_zoom(manual_zoom.unwrap_or(mode_zoom));
+
         if engine.platform.input.is_key_pressed(Keycode::F5) {
+            let respawn_point = level_manager.get_respawn_point();
             for (_entity, (player, transform)) in
                 engine.world.query_mut::<(&mut Player, &mut Transform)>()
             {
-                transform.position = level.spawn_point;
+                transform.position = respawn_point;
                 player.health = player.max_health;
                 player.energy = player.max_energy;
             }
@@ -272,34 +629,39 @@ fn main() -> Result<()> {
 
         if engine.platform.input.is_key_pressed(Keycode::F3) {
             ui_manager.toggle_debug();
+            settings.show_debug_overlay = ui_manager.debug_overlay.is_visible();
+            save_progress(&save_store, active_slot, &level_manager, &lives, &hangar.loadout, &settings);
             info!("Toggled debug overlay");
         }
 
-        engine.renderer.camera.update(delta_time);
+        if engine.platform.input.is_key_pressed(Keycode::F7) {
+            settings.screen_shake = !settings.screen_shake;
+            let label = if settings.screen_shake { "SCREEN SHAKE ON" } else { "SCREEN SHAKE OFF" };
+            ui_manager.show_message(label, 1.5);
+            save_progress(&save_store, active_slot, &level_manager, &lives, &hangar.loadout, &settings);
+        }
+
+        if engine.platform.input.is_key_pressed(Keycode::F8) {
+            settings.pixel_scaling = !settings.pixel_scaling;
+            apply_pixel_scaling(&mut engine.renderer, settings.pixel_scaling);
+            let label = if settings.pixel_scaling { "PIXEL SCALING ON" } else { "PIXEL SCALING OFF" };
+            ui_manager.show_message(label, 1.5);
+            save_progress(&save_store, active_slot, &level_manager, &lives, &hangar.loadout, &settings);
+        }
 
         // Camera follow logic based on current mode
-        let follow_entity = if current_play_mode == PlayState::InMech {
+        let follow_entity = if matches!(current_play_mode, PlayState::InMech | PlayState::Transition) {
             current_mech_entity
         } else {
             current_pilot_entity
         };
         
-        if let Some(entity) = follow_entity {
-            if let Ok(transform) = engine.world.get::<&Transform>(entity) {
-                // Follow entity horizontally, but limit vertical movement to keep ground visible
-                let ground_y = (level.height - 3) as f32 * TILE_SIZE;
-                let min_camera_y = ground_y - 720.0 / engine.renderer.camera.zoom / 2.0 + 100.0;
-                
-                let target_pos = Vec2::new(
-                    transform.position.x,
-                    transform.position.y.max(min_camera_y)
-                );
-                
-                engine
-                    .renderer
-                    .camera
-                    .follow(target_pos, 5.0, delta_time);
-            }
+        let followed = follow_entity.and_then(|entity| {
+            let mut query = engine.world.query_one::<(&Transform, Option<&RigidBody>)>(entity).ok()?;
+            query.get().map(|(transform, body)| (transform.position, body.map_or(Vec2::ZERO, |body| body.velocity)))
+        });
+        if let Some((target, velocity)) = followed {
+            camera_controller.update(&mut engine.renderer.camera, target, velocity, delta_time);
         }
 
         // === LAYERED RENDERING ===
@@ -315,14 +677,19 @@ fn main() -> Result<()> {
                 rect.y + rect.height / 2.0,
             ));
             let sprite = Sprite::new(Vec2::new(rect.width, rect.height), *color);
-            engine.renderer.draw_sprite(&sprite, &transform);
+            // The gradient spans the whole level, so it scrolls with the world and always sits behind the clouds
+            render_queue.push(
+                DrawCommand::new(RenderLayer::FarBackground, sprite, transform)
+                    .with_sort_key(f32::MIN)
+                    .with_parallax(ParallaxConfig::new(1.0, 1.0))
+                    .untinted(),
+            );
         }
         
-        // Layer 2: Clouds (with slight parallax and day/night tinting)
+        // Layer 2: Clouds (far background parallax and day/night tinting)
         let ambient = day_night_cycle.get_ambient_light();
         for (pos, radius, color) in &level.clouds {
-            let parallax_offset = engine.renderer.camera.position * 0.05;
-            let cloud_transform = Transform::new(*pos - parallax_offset);
+            let cloud_transform = Transform::new(*pos);
             
             // Tint clouds based on time of day
             let tinted_color = Color::new(
@@ -333,51 +700,25 @@ fn main() -> Result<()> {
             );
             
             let cloud_sprite = Sprite::new(Vec2::new(*radius * 2.0, *radius * 1.5), tinted_color);
-            engine.renderer.draw_sprite(&cloud_sprite, &cloud_transform);
+            render_queue.submit(RenderLayer::FarBackground, cloud_sprite, cloud_transform);
         }
         
-        // Buildings and props are disabled for now
-        // They can be re-enabled later when needed
-
-        // Layer 7: Gameplay tiles - optimized rendering with proper alignment
-        // Calculate visible tile range to avoid rendering off-screen tiles
-        let cam_pos = engine.renderer.camera.position;
-        let zoom = engine.renderer.camera.zoom;
-        let viewport = engine.renderer.camera.viewport_size;
-        
-        let min_x = ((cam_pos.x - viewport.x / zoom / 2.0) / TILE_SIZE).floor() as usize;
-        let max_x = ((cam_pos.x + viewport.x / zoom / 2.0) / TILE_SIZE).ceil() as usize + 1;
-        let min_y = ((cam_pos.y - viewport.y / zoom / 2.0) / TILE_SIZE).floor() as usize;
-        let max_y = ((cam_pos.y + viewport.y / zoom / 2.0) / TILE_SIZE).ceil() as usize + 1;
-        
-        // Clamp to level bounds
-        let min_x = min_x.max(0);
-        let max_x = max_x.min(level.width);
-        let min_y = min_y.max(0);
-        let max_y = max_y.min(level.height);
-        
-        for y in min_y..max_y {
-            for x in min_x..max_x {
-                if let Some(tile) = level.get_tile(x, y) {
-                    if tile.tile_type != game::level::TileType::Empty {
-                        // Use precise tile positioning to ensure alignment
-                        let tile_transform = Transform::new(Vec2::new(
-                            (x as f32 + 0.5) * TILE_SIZE,
-                            (y as f32 + 0.5) * TILE_SIZE,
-                        ));
-                        // Add a small overlap to prevent gaps (slightly larger than TILE_SIZE)
-                        let tile_sprite = Sprite::new(Vec2::new(TILE_SIZE + 0.5, TILE_SIZE + 0.5), tile.color);
-                        engine.renderer.draw_sprite(&tile_sprite, &tile_transform);
-                    }
-                }
-            }
-        }
+        // Layers 3-6: City skylines, street-level buildings and props, windows lit by time of day
+        submit_city(&mut render_queue, level, &engine.renderer.camera, &day_night_cycle);
+
+        // Layer 7: Gameplay tiles, drawn from cached chunk textures that are rebuilt only when tiles change
+        tile_chunks.submit(&mut render_queue, &mut engine.renderer, level);
 
-        // Layer 8: Entities (player, enemies)
-        for (_entity, (transform, sprite)) in engine.world.query::<(&Transform, &Sprite)>().iter() {
-            engine.renderer.draw_sprite(sprite, transform);
+        // Layer 8: Entities (player, enemies), or whichever layer the entity asks for
+        for (_entity, (transform, sprite, layer)) in engine.world.query::<(&Transform, &Sprite, Option<&RenderLayer>)>().iter() {
+            let layer = layer.copied().unwrap_or(RenderLayer::Entities);
+            render_queue.submit(layer, sprite.clone(), *transform);
         }
 
+        // Raised mech shields and stomp warnings sit just above the entities
+        draw_mech_shields(&mut render_queue, &engine.world);
+        draw_stomp_telegraphs(&mut render_queue, &engine.world, level);
+
         // Layer 8.5: Enemy health bars (rendered above enemies but below UI)
         for (_entity, (enemy, transform)) in engine.world.query::<(&Enemy, &Transform)>().iter() {
             if enemy.should_show_health_bar() && enemy.health > 0.0 {
@@ -399,7 +740,7 @@ fn main() -> Result<()> {
                     Vec2::new(bar_width, bar_height),
                     Color::new(80, 20, 20, 200)
                 );
-                engine.renderer.draw_sprite(&bg_sprite, &bg_transform);
+                render_queue.submit(RenderLayer::Effects, bg_sprite, bg_transform);
                 
                 // Draw health fill (bright red)
                 let health_ratio = enemy.health / enemy.max_health;
@@ -413,7 +754,7 @@ fn main() -> Result<()> {
                         Vec2::new(fill_width, bar_height),
                         Color::new(255, 60, 60, 200)
                     );
-                    engine.renderer.draw_sprite(&fill_sprite, &fill_transform);
+                    render_queue.submit(RenderLayer::Effects, fill_sprite, fill_transform);
                 }
                 
                 // Draw border (white)
@@ -427,14 +768,14 @@ fn main() -> Result<()> {
                     transform.position.x,
                     transform.position.y - bar_offset_y - bar_height / 2.0 - border_thickness / 2.0
                 ));
-                engine.renderer.draw_sprite(&top_border, &top_transform);
+                render_queue.submit(RenderLayer::Effects, top_border.clone(), top_transform);
                 
                 // Bottom border
                 let bottom_transform = Transform::new(Vec2::new(
                     transform.position.x,
                     transform.position.y - bar_offset_y + bar_height / 2.0 + border_thickness / 2.0
                 ));
-                engine.renderer.draw_sprite(&top_border, &bottom_transform);
+                render_queue.submit(RenderLayer::Effects, top_border, bottom_transform);
                 
                 // Left border
                 let side_border = Sprite::new(
@@ -445,17 +786,30 @@ fn main() -> Result<()> {
                     transform.position.x - bar_width / 2.0 - border_thickness / 2.0,
                     transform.position.y - bar_offset_y
                 ));
-                engine.renderer.draw_sprite(&side_border, &left_transform);
+                render_queue.submit(RenderLayer::Effects, side_border.clone(), left_transform);
                 
                 // Right border
                 let right_transform = Transform::new(Vec2::new(
                     transform.position.x + bar_width / 2.0 + border_thickness / 2.0,
                     transform.position.y - bar_offset_y
                 ));
-                engine.renderer.draw_sprite(&side_border, &right_transform);
+                render_queue.submit(RenderLayer::Effects, side_border, right_transform);
             }
         }
 
+        render_queue.advance(delta_time);
+        render_queue.flush(&mut engine.renderer);
+
+        // Darken the world for the time of day, except where lights reach past the solid tiles
+        collect_lights(&engine.world, &mut light_map);
+        submit_city_lights(&mut light_map, level, &engine.renderer.camera, &day_night_cycle);
+        let darkness = day_night_cycle.get_ambient_light().lerp(Color::WHITE, 0.3);
+        light_map.render(&mut engine.renderer, darkness, |light, point| !level.line_of_sight(light, point));
+
+        if ui_manager.debug_overlay.is_visible() {
+            draw_trigger_volumes(&mut engine.renderer, level);
+        }
+
         // Layer 9: UI Elements (always on top)
         let mut player_health = 100.0;
         let mut player_max_health = 100.0;
@@ -475,6 +829,8 @@ fn main() -> Result<()> {
         }
         
         // Get stats based on current play mode
+        let mut mech_power = None;
+        let mut stomp_readiness = None;
         if current_play_mode == PlayState::InMech {
             // Get mech stats
             if let Some(mech_entity) = current_mech_entity {
@@ -490,6 +846,12 @@ fn main() -> Result<()> {
                 if let Ok(body) = engine.world.get::<&RB>(mech_entity) {
                     player_velocity = Some(body.velocity);
                 }
+                if let Ok((allocation, shield)) = engine.world.query_one_mut::<(&EnergyAllocation, &MechShield)>(mech_entity) {
+                    mech_power = Some((allocation.clone(), shield.clone()));
+                }
+                if let Ok(controller) = engine.world.get::<&MechController>(mech_entity) {
+                    stomp_readiness = Some(controller.stomp_readiness());
+                }
             }
         } else {
             // Get player stats
@@ -531,6 +893,9 @@ fn main() -> Result<()> {
             player_velocity,
         );
         
+        ui_manager.set_mech_power(mech_power);
+        ui_manager.set_stomp_readiness(stomp_readiness);
+        ui_manager.set_lives(lives.lives, lives.continues);
         ui_manager.render(&mut engine.renderer);
         
         // Render minimap
@@ -544,13 +909,18 @@ fn main() -> Result<()> {
             
             let camera_pos = engine.renderer.camera.position;
             let goal_pos = level.goal_position;
+            let companions = companion_markers(&engine.world);
             
+            let markers = MinimapMarkers {
+                enemies: &enemy_positions,
+                collectibles: &collectibles,
+                goal: goal_pos,
+                companions: &companions,
+            };
             minimap.render(
                 &mut engine.renderer,
                 player_position,
-                &enemy_positions,
-                &collectibles,
-                goal_pos,
+                &markers,
                 Vec2::ZERO,  // Camera offset should be zero for screen-space UI
             );
         }
@@ -559,3 +929,115 @@ fn main() -> Result<()> {
     info!("Shutting down...");
     Ok(())
 }
+
+/// Switch between drawing at the window's size and at the design resolution, logging rather than interrupting play on failure
+fn apply_pixel_scaling(renderer: &mut Renderer, enabled: bool) {
+    if let Err(e) = renderer.set_virtual_resolution(enabled.then_some(DESIGN_RESOLUTION)) {
+        log::warn!("Failed to change resolution: {:#}", e);
+    }
+}
+
+/// Turn a mech weapon shot into a live projectile
+fn spawn_mech_shot(world: &mut hecs::World, shot: MechShot) {
+    let mut body = RigidBody::new(0.1);
+    body.velocity = shot.direction * shot.weapon.projectile_speed;
+
+    let projectile = shot.weapon.to_projectile();
+
+    world.spawn((
+        projectile.clone(),
+        Transform::new(shot.position),
+        Sprite::new(projectile.size, projectile.color),
+        body,
+        Collider::Circle { radius: projectile.size.x / 2.0 },
+        CollisionFilter::friendly_projectile(),
+    ));
+    let flash = PointLight::new(220.0, Color::new(255, 210, 140, 255)).with_intensity(1.3);
+    spawn_light_flash(world, shot.position, flash, 0.08);
+}
+
+/// Spawn points for enemies (off-screen left and right of the player spawn)
+fn enemy_spawn_points(spawn_pos: Vec2) -> Vec<Vec2> {
+    vec![
+        Vec2::new(spawn_pos.x - 800.0, spawn_pos.y),  // Far left
+        Vec2::new(spawn_pos.x + 800.0, spawn_pos.y),  // Far right
+        Vec2::new(spawn_pos.x - 1000.0, spawn_pos.y - 100.0), // Upper left
+        Vec2::new(spawn_pos.x + 1000.0, spawn_pos.y - 100.0), // Upper right
+    ]
+}
+
+/// Write the campaign to the active save slot, logging rather than interrupting play on failure
+fn save_progress(
+    store: &SaveStore,
+    slot: usize,
+    level_manager: &LevelManager,
+    lives: &Lives,
+    loadout: &MechLoadout,
+    settings: &Settings,
+) {
+    let save = SaveGame::capture(level_manager, lives, loadout, settings);
+    if let Err(e) = store.save(slot, &save) {
+        log::warn!("Failed to save game: {:#}", e);
+    }
+}
+
+/// Reset the world for the level the manager is on and return a spawner for it
+fn start_level(
+    world: &mut hecs::World,
+    camera: &mut Camera,
+    camera_controller: &mut CameraController,
+    level_manager: &LevelManager,
+    tile_chunks: &mut TileChunkCache,
+    player_entity: hecs::Entity,
+    mech_entity: hecs::Entity,
+) -> EnemySpawner {
+    let level = level_manager.get_current_level();
+    info!("Starting {}", level.name);
+    reset_world_for_level(world, level, player_entity, mech_entity);
+    // Restarts keep the level name, so the cache can't tell the tiles were reset
+    tile_chunks.invalidate_all();
+    camera_controller.set_bounds(level.camera_bounds());
+    camera_controller.set_target_zoom(ON_FOOT_ZOOM);
+    camera_controller.snap_to(camera, level.spawn_point);
+    spawn_collectibles(world, level_manager);
+    spawn_checkpoints(world, level_manager);
+    EnemySpawner::new(enemy_spawn_points(level.spawn_point))
+}
+
+/// Clear out the previous level's enemies and effects and move the player and mech to the new spawn
+fn reset_world_for_level(world: &mut hecs::World, level: &Level, player_entity: hecs::Entity, mech_entity: hecs::Entity) {
+    let leftovers: Vec<hecs::Entity> = world
+        .iter()
+        .filter(|entity| {
+            entity.has::<Enemy>()
+                || entity.has::<CollectibleMarker>()
+                || entity.has::<CheckpointMarker>()
+                || entity.has::<Projectile>()
+                || entity.has::<systems::particles::Particle>()
+        })
+        .map(|entity| entity.entity())
+        .collect();
+    for entity in leftovers {
+        let _ = world.despawn(entity);
+    }
+
+    if let Ok((player, transform, body)) = world.query_one_mut::<(&mut Player, &mut Transform, &mut RigidBody)>(player_entity) {
+        transform.position = level.spawn_point;
+        body.velocity = Vec2::ZERO;
+        player.health = player.max_health;
+        player.energy = player.max_energy;
+    }
+
+    if let Ok((mech, transform, body)) = world.query_one_mut::<(&Mech, &mut Transform, &mut RigidBody)>(mech_entity) {
+        // A piloted mech deploys at the spawn point, an empty one waits next to it
+        let offset = if mech.is_occupied { Vec2::ZERO } else { Vec2::new(200.0, 0.0) };
+        transform.position = level.spawn_point + offset;
+        body.velocity = Vec2::ZERO;
+    }
+
+    // Old orders point into the previous level
+    let parked_at = world.get::<&Transform>(mech_entity).map(|t| t.position).ok();
+    if let (Some(position), Ok(mut companion)) = (parked_at, world.get::<&mut MechCompanion>(mech_entity)) {
+        companion.set_command(CompanionCommand::HoldPosition(position));
+    }
+}
@@ -0,0 +1,108 @@
+use crate::engine::physics::{CharacterBody, RigidBody};
+use crate::engine::rendering::{AnimationClip, Animator, Sprite, SpriteSheet};
+use crate::systems::mech::{Mech, MechController};
+use crate::systems::player::{Player, PlayerController};
+
+pub const IDLE: &str = "idle";
+pub const RUN: &str = "run";
+pub const JUMP: &str = "jump";
+pub const FALL: &str = "fall";
+pub const SHOOT: &str = "shoot";
+pub const HURT: &str = "hurt";
+pub const WALK: &str = "walk";
+pub const BOOST: &str = "boost";
+pub const STOMP: &str = "stomp";
+
+/// Raised when a foot hits the ground in a run or walk cycle
+pub const FOOTSTEP: &str = "footstep";
+
+/// Slower than this counts as standing still
+const MOVING_SPEED: f32 = 5.0;
+
+/// Remembers the last health seen so a drop can play the hurt clip
+#[derive(Debug, Clone)]
+pub struct HitReaction {
+    pub last_health: f32,
+}
+
+/// Player clips. Sheet layout: idle 0-3, run 4-9, jump 10-11, fall 12-13, shoot 14-15, hurt 16-17.
+pub fn player_animator(sheet: Option<SpriteSheet>) -> Animator {
+    Animator::new(IDLE)
+        .with_sheet(sheet)
+        .with_clip(IDLE, AnimationClip::new(0, 4, 6.0))
+        .with_clip(RUN, AnimationClip::new(4, 6, 12.0).with_event(1, FOOTSTEP).with_event(4, FOOTSTEP))
+        .with_clip(JUMP, AnimationClip::new(10, 2, 10.0).once())
+        .with_clip(FALL, AnimationClip::new(12, 2, 8.0))
+        .with_clip(SHOOT, AnimationClip::new(14, 2, 16.0).once())
+        .with_clip(HURT, AnimationClip::new(16, 2, 8.0).once())
+}
+
+/// Mech clips. Sheet layout: idle 0-1, walk 2-7, boost 8-9, stomp 10-12.
+pub fn mech_animator(sheet: Option<SpriteSheet>) -> Animator {
+    Animator::new(IDLE)
+        .with_sheet(sheet)
+        .with_clip(IDLE, AnimationClip::new(0, 2, 2.0))
+        .with_clip(WALK, AnimationClip::new(2, 6, 8.0).with_event(0, FOOTSTEP).with_event(3, FOOTSTEP))
+        .with_clip(BOOST, AnimationClip::new(8, 2, 12.0))
+        .with_clip(STOMP, AnimationClip::new(10, 3, 10.0).once())
+}
+
+/// Picks the player's clip: hurt beats shooting, which beats jumping, falling, running and idling.
+/// One-shot clips play out unless something more urgent happens. Sheets face right and are mirrored to face left.
+pub fn player_animation_system(world: &mut hecs::World) {
+    for (_entity, (player, controller, body, animator, reaction, sprite)) in world.query_mut::<(
+        &Player,
+        &PlayerController,
+        &RigidBody,
+        &mut Animator,
+        &mut HitReaction,
+        &mut Sprite,
+    )>() {
+        sprite.flip_x = controller.facing < 0.0;
+        let hurt = player.health < reaction.last_health;
+        reaction.last_health = player.health;
+
+        if hurt {
+            animator.restart(HURT);
+        } else if controller.just_fired && animator.state() != HURT {
+            if animator.state() == SHOOT && animator.is_finished() {
+                animator.restart(SHOOT);
+            } else {
+                animator.play(SHOOT);
+            }
+        } else if !animator.is_busy() {
+            let state = if !controller.is_grounded {
+                if body.velocity.y < 0.0 { JUMP } else { FALL }
+            } else if body.velocity.x.abs() > MOVING_SPEED {
+                RUN
+            } else {
+                IDLE
+            };
+            animator.play(state);
+        }
+    }
+}
+
+/// Picks each mech's clip from its controller (stomping, boosting, walking or idling) and turns it to face its heading
+pub fn mech_animation_system(world: &mut hecs::World) {
+    for (_entity, (_mech, controller, body, character, animator, sprite)) in world.query_mut::<(
+        &Mech,
+        &MechController,
+        &RigidBody,
+        &CharacterBody,
+        &mut Animator,
+        &mut Sprite,
+    )>() {
+        sprite.flip_x = controller.facing < 0.0;
+        let state = if controller.is_stomping {
+            STOMP
+        } else if controller.is_boosting {
+            BOOST
+        } else if character.is_grounded() && body.velocity.x.abs() > MOVING_SPEED {
+            WALK
+        } else {
+            IDLE
+        };
+        animator.play(state);
+    }
+}
@@ -1,13 +1,19 @@
-use crate::engine::core::{Rect, Transform};
-use crate::engine::physics::RigidBody;
-use crate::game::Level;
+use crate::engine::core::Transform;
+use crate::engine::physics::{BodyContact, CharacterBody, RigidBody};
+use crate::systems::mech::{apply_mech_damage, Mech};
+use crate::systems::particles::{Particle, ParticleSystem};
+use crate::systems::player::{Invulnerable, Player};
 use glam::Vec2;
+use hecs::World;
 use rand::Rng;
 
+/// How far below an enemy the player has to be before it drops off a platform after them
+const PLATFORM_DROP_HEIGHT: f32 = 64.0;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum EnemyType {
     Ranger,      // Current type - maintains distance and shoots
-    Rusher,      // Future type - charges at player
+    Rusher,      // Charges at the player and hurts on contact
     Sniper,      // Future type - long range, slow fire
     Tank,        // Future type - slow, high health
 }
@@ -26,6 +32,11 @@ pub struct Enemy {
     pub projectile_speed: f32,
     pub damage: f32,
     pub health_bar_timer: f32,
+    pub stun_timer: f32,
+    /// Damage dealt by running into the player or a mech
+    pub contact_damage: f32,
+    pub contact_cooldown: f32,
+    pub contact_timer: f32,
 }
 
 impl Enemy {
@@ -47,6 +58,31 @@ impl Enemy {
             projectile_speed: 600.0,  // Increased for better accuracy
             damage: 10.0,
             health_bar_timer: 0.0,
+            stun_timer: 0.0,
+            contact_damage: 0.0,
+            contact_cooldown: 0.0,
+            contact_timer: 0.0,
+        }
+    }
+
+    pub fn rusher() -> Self {
+        Self {
+            enemy_type: EnemyType::Rusher,
+            size: Vec2::new(28.0, 40.0),
+            health: 35.0,
+            max_health: 35.0,
+            attack_range: 900.0,      // Starts charging from this far away
+            optimal_distance: 0.0,    // Wants to be right on top of the player
+            movement_speed: 280.0,
+            shoot_cooldown: 0.0,
+            shoot_timer: 0.0,
+            projectile_speed: 0.0,
+            damage: 0.0,
+            health_bar_timer: 0.0,
+            stun_timer: 0.0,
+            contact_damage: 15.0,
+            contact_cooldown: 1.0,    // Seconds between hits while in contact
+            contact_timer: 0.0,
         }
     }
 
@@ -62,6 +98,13 @@ impl Enemy {
         if self.shoot_timer > 0.0 {
             self.shoot_timer -= delta_time;
         }
+        if self.contact_timer > 0.0 {
+            self.contact_timer -= delta_time;
+        }
+    }
+
+    pub fn can_hit_on_contact(&self) -> bool {
+        self.contact_damage > 0.0 && self.contact_timer <= 0.0
     }
     
     pub fn show_health_bar(&mut self) {
@@ -77,6 +120,21 @@ impl Enemy {
     pub fn should_show_health_bar(&self) -> bool {
         self.health_bar_timer > 0.0
     }
+
+    /// Knock the enemy off balance - it can't move or shoot until the timer runs out
+    pub fn stun(&mut self, duration: f32) {
+        self.stun_timer = self.stun_timer.max(duration);
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.stun_timer > 0.0
+    }
+}
+
+impl Default for Enemy {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -214,6 +272,12 @@ impl EnemyController {
     }
 }
 
+impl Default for EnemyController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub fn enemy_ai_system(
     world: &mut hecs::World,
     delta_time: f32,
@@ -230,14 +294,22 @@ pub fn enemy_ai_system(
     }
 
     if let Some((player_position, player_velocity)) = player_info {
-        for (_entity, (enemy, transform, controller, body)) in world.query_mut::<(
+        for (_entity, (enemy, transform, controller, body, character)) in world.query_mut::<(
             &mut Enemy,
             &mut Transform,
             &mut EnemyController,
             &mut RigidBody,
+            &mut CharacterBody,
         )>() {
             enemy.update_timer(delta_time);
             enemy.update_health_bar_timer(delta_time);
+
+            // Stunned enemies just slide to a halt
+            if enemy.is_stunned() {
+                enemy.stun_timer -= delta_time;
+                body.velocity.x *= (1.0 - 3.0 * delta_time).max(0.0);
+                continue;
+            }
             
             controller.update_movement_and_targeting(
                 player_position, 
@@ -253,9 +325,21 @@ pub fn enemy_ai_system(
             let move_velocity = controller.movement_direction * enemy.movement_speed;
             body.velocity.x = move_velocity.x;
             // Don't override Y velocity to preserve gravity
+
+            // Rushers charge flat out instead of keeping their distance
+            if enemy.enemy_type == EnemyType::Rusher {
+                let dx = player_position.x - transform.position.x;
+                body.velocity.x = if dx.abs() <= enemy.attack_range { dx.signum() * enemy.movement_speed } else { 0.0 };
+            }
+
+            // Drop off platforms to get at a player on the floor below
+            if player_position.y > transform.position.y + PLATFORM_DROP_HEIGHT {
+                character.drop_through();
+            }
             
             // Check if can shoot based on state
-            let can_shoot = matches!(controller.state, EnemyState::Strafing | EnemyState::Pursuing | EnemyState::Retreating);
+            let can_shoot = matches!(controller.state, EnemyState::Strafing | EnemyState::Pursuing | EnemyState::Retreating)
+                && enemy.enemy_type != EnemyType::Rusher;
             if can_shoot && enemy.can_shoot() {
                 // Calculate proper projectile velocity with gravity compensation
                 let projectile_velocity = controller.calculate_projectile_velocity(
@@ -284,51 +368,42 @@ pub fn enemy_ai_system(
     projectiles_to_spawn
 }
 
-pub fn enemy_physics_system(
-    world: &mut hecs::World,
-    level: &Level,
-    delta_time: f32,
-) {
-    for (_entity, (enemy, transform, body)) in world.query_mut::<(
-        &Enemy,
-        &mut Transform,
-        &mut RigidBody,
-    )>()
-    .without::<&crate::systems::player::Player>()
-    {
-        // Apply gravity
-        body.apply_force(Vec2::new(0.0, 800.0));
-        body.update(delta_time);
+/// Enemies with contact damage hurt the player or a mech they run into, then bounce off
+pub fn enemy_contact_damage_system(world: &mut World, contacts: &[BodyContact]) -> Vec<Particle> {
+    let mut particles = Vec::new();
 
-        let new_x = transform.position.x + body.velocity.x * delta_time;
-        let new_y = transform.position.y + body.velocity.y * delta_time;
-
-        // Check horizontal collision
-        let enemy_rect_x = Rect::new(
-            new_x - enemy.size.x / 2.0,
-            transform.position.y - enemy.size.y / 2.0,
-            enemy.size.x,
-            enemy.size.y,
-        );
+    for contact in contacts {
+        for (attacker, target, direction) in [(contact.a, contact.b, contact.direction), (contact.b, contact.a, -contact.direction)] {
+            let is_target = world.satisfies::<&Player>(target).unwrap_or(false)
+                || world.satisfies::<&Mech>(target).unwrap_or(false);
+            if !is_target || world.satisfies::<&Invulnerable>(target).unwrap_or(false) {
+                continue;
+            }
 
-        if !level.check_collision(enemy_rect_x) {
-            transform.position.x = new_x;
-        } else {
-            body.velocity.x = 0.0;
-        }
+            let damage = match world.get::<&mut Enemy>(attacker) {
+                Ok(mut enemy) if enemy.can_hit_on_contact() => {
+                    enemy.contact_timer = enemy.contact_cooldown;
+                    enemy.contact_damage
+                }
+                _ => continue,
+            };
 
-        // Check vertical collision
-        let enemy_rect_y = Rect::new(
-            transform.position.x - enemy.size.x / 2.0,
-            new_y - enemy.size.y / 2.0,
-            enemy.size.x,
-            enemy.size.y,
-        );
+            let hit_direction = Vec2::new(direction, 0.0);
+            if let Ok(player) = world.query_one_mut::<&mut Player>(target) {
+                player.health = (player.health - damage).max(0.0);
+            } else {
+                apply_mech_damage(world, target, damage, hit_direction);
+            }
 
-        if !level.check_collision(enemy_rect_y) {
-            transform.position.y = new_y;
-        } else {
-            body.velocity.y = 0.0;
+            // Bounce back for another run
+            if let Ok(mut body) = world.get::<&mut RigidBody>(attacker) {
+                body.velocity = Vec2::new(-direction * 300.0, -250.0);
+            }
+            if let Ok(transform) = world.get::<&Transform>(target) {
+                particles.extend(ParticleSystem::create_impact_particles(transform.position, -hit_direction, 8));
+            }
         }
     }
-}
\ No newline at end of file
+
+    particles
+}
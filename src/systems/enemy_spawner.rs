@@ -1,11 +1,14 @@
 use crate::engine::core::{Color, Transform};
-use crate::engine::physics::{Collider, RigidBody};
+use crate::engine::physics::{CharacterBody, Collider, CollisionFilter, RigidBody};
 use crate::engine::rendering::Sprite;
 use crate::systems::enemy::{Enemy, EnemyController};
 use glam::Vec2;
-use hecs::World;
+use hecs::{Entity, World};
 use rand::Rng;
 
+/// Chance each spawned enemy is a rusher rather than a ranger
+const RUSHER_CHANCE: f64 = 0.35;
+
 pub struct EnemySpawner {
     spawn_timer: f32,
     spawn_interval: f32,
@@ -61,33 +64,52 @@ impl EnemySpawner {
         // Add some randomness to spawn position
         let offset_x = rng.gen_range(-50.0..50.0);
         let spawn_pos = Vec2::new(base_pos.x + offset_x, base_pos.y);
-        
-        // Create enemy with scaled stats based on difficulty
-        let mut enemy = Enemy::ranger(); // Use Ranger type explicitly
-        enemy.health *= self.difficulty_multiplier;
-        enemy.max_health *= self.difficulty_multiplier;
-        enemy.damage *= self.difficulty_multiplier;
-        
-        // Vary enemy appearance slightly
-        let size_variation = rng.gen_range(0.9..1.1);
-        let enemy_size = enemy.size * size_variation;
-        
-        // Vary enemy color slightly (shades of red/orange)
-        let red = rng.gen_range(200..255);
-        let green = rng.gen_range(30..80);
-        let blue = rng.gen_range(30..80);
-        
-        world.spawn((
-            enemy,
-            Transform::new(spawn_pos),
-            Sprite::new(enemy_size, Color::new(red, green, blue, 255)),
-            RigidBody::new(1.0),
-            Collider::Box { size: enemy_size },
-            EnemyController::new(),
-        ));
+        spawn_enemy_at(world, spawn_pos, self.difficulty_multiplier);
+    }
+
+    /// Drop a group of enemies in around a position, scaled to the current difficulty
+    pub fn spawn_wave(&self, world: &mut World, position: Vec2, count: usize, spread: f32) {
+        let mut rng = rand::thread_rng();
+        for _ in 0..count {
+            let offset_x = if spread > 0.0 { rng.gen_range(-spread..spread) } else { 0.0 };
+            spawn_enemy_at(world, Vec2::new(position.x + offset_x, position.y), self.difficulty_multiplier);
+        }
     }
     
     pub fn get_spawn_wave_info(&self) -> (f32, usize, f32) {
         (self.spawn_interval, self.max_enemies, self.difficulty_multiplier)
     }
+}
+
+/// Spawn a single ranger or rusher with stats scaled by `difficulty_multiplier`
+pub fn spawn_enemy_at(world: &mut World, spawn_pos: Vec2, difficulty_multiplier: f32) -> Entity {
+    let mut rng = rand::thread_rng();
+
+    // Create enemy with scaled stats based on difficulty - roughly one in three charges in as a rusher
+    let is_rusher = rng.gen_bool(RUSHER_CHANCE);
+    let mut enemy = if is_rusher { Enemy::rusher() } else { Enemy::ranger() };
+    enemy.health *= difficulty_multiplier;
+    enemy.max_health *= difficulty_multiplier;
+    enemy.damage *= difficulty_multiplier;
+    enemy.contact_damage *= difficulty_multiplier;
+    
+    // Vary enemy appearance slightly
+    let size_variation = rng.gen_range(0.9..1.1);
+    let enemy_size = enemy.size * size_variation;
+    
+    // Vary enemy color slightly (shades of red, rushers in yellow)
+    let red = rng.gen_range(200..255);
+    let green = if is_rusher { rng.gen_range(180..220) } else { rng.gen_range(30..80) };
+    let blue = rng.gen_range(30..80);
+    
+    world.spawn((
+        enemy,
+        Transform::new(spawn_pos),
+        Sprite::new(enemy_size, Color::new(red, green, blue, 255)),
+        RigidBody::new(1.0),
+        Collider::Box { size: enemy_size },
+        CharacterBody::new(enemy_size, 800.0),
+        CollisionFilter::enemy(),
+        EnemyController::new(),
+    ))
 }
\ No newline at end of file
@@ -0,0 +1,61 @@
+use crate::engine::core::{Color, Transform};
+use crate::engine::rendering::{LightFlash, LightMap, PointLight};
+use crate::systems::mech::{Mech, MechController};
+use crate::systems::projectile::Projectile;
+use glam::Vec2;
+use hecs::{Entity, World};
+
+/// Half the width of a mech headlight beam, in radians
+const HEADLIGHT_HALF_ANGLE: f32 = 0.45;
+
+/// Light for a mech's headlight, aimed to the right until its mech turns
+pub fn mech_headlight(mech: &Mech) -> PointLight {
+    PointLight::new(420.0, Color::new(255, 240, 200, 255))
+        .with_intensity(1.2)
+        .with_offset(Vec2::new(mech.size.x * 0.3, -mech.size.y * 0.25))
+        .with_cone(Vec2::X, HEADLIGHT_HALF_ANGLE)
+}
+
+/// A quick burst of light, e.g. a muzzle flash or explosion
+pub fn spawn_light_flash(world: &mut World, position: Vec2, light: PointLight, duration: f32) {
+    let peak = light.intensity;
+    world.spawn((Transform::new(position), light, LightFlash::new(duration, peak)));
+}
+
+/// Fades flashes out and returns the ones that have burnt out
+pub fn light_flash_system(world: &mut World, delta_time: f32) -> Vec<Entity> {
+    let mut expired = Vec::new();
+    for (entity, (flash, light)) in world.query_mut::<(&mut LightFlash, &mut PointLight)>() {
+        flash.timer -= delta_time;
+        if flash.timer <= 0.0 {
+            expired.push(entity);
+        } else {
+            light.intensity = flash.peak * flash.timer / flash.duration;
+        }
+    }
+    expired
+}
+
+/// Keeps headlights pointing the way their mech faces
+pub fn mech_headlight_system(world: &mut World) {
+    for (_entity, (mech, controller, light)) in world.query_mut::<(&Mech, &MechController, &mut PointLight)>() {
+        let facing = if controller.facing < 0.0 { -1.0 } else { 1.0 };
+        light.offset.x = facing * mech.size.x * 0.3;
+        if let Some(cone) = &mut light.cone {
+            cone.direction = Vec2::new(facing, 0.0);
+        }
+    }
+}
+
+/// Gather every entity light plus the glow of projectiles that cast one
+pub fn collect_lights(world: &World, lights: &mut LightMap) {
+    for (_entity, (transform, light)) in world.query::<(&Transform, &PointLight)>().iter() {
+        lights.add(transform.position + light.offset, *light);
+    }
+
+    for (_entity, (transform, projectile)) in world.query::<(&Transform, &Projectile)>().iter() {
+        if projectile.glow_radius > 0.0 {
+            lights.add(transform.position, PointLight::new(projectile.glow_radius, projectile.color).without_shadows());
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::sync::OnceLock;
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// Chassis id used when nothing else is specified
+pub const DEFAULT_CHASSIS: &str = "titan";
+
+/// Special movement ability granted by a chassis
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MechMobility {
+    None,
+    /// Short burst of horizontal speed triggered by tapping boost
+    Dash {
+        speed: f32,
+        duration: f32,
+        cooldown: f32,
+        energy_cost: f32,
+    },
+}
+
+/// Data-driven mech frame definition (see assets/data/mech_chassis.ron)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChassisDef {
+    pub id: String,
+    pub name: String,
+    pub size: Vec2,
+    pub mass: f32,
+    pub health: f32,
+    pub energy: f32,
+    pub normal_speed: f32,
+    pub boost_speed: f32,
+    pub jump_power: f32,
+    pub acceleration: f32,
+    pub gravity: f32,
+    pub hardpoints: usize,
+    pub crushes_tiles: bool,
+    pub mobility: MechMobility,
+}
+
+pub struct ChassisRegistry {
+    chassis: Vec<ChassisDef>,
+}
+
+impl ChassisRegistry {
+    pub fn from_ron(source: &str) -> anyhow::Result<Self> {
+        let chassis: Vec<ChassisDef> = ron::from_str(source)?;
+        if chassis.is_empty() {
+            anyhow::bail!("No chassis definitions found");
+        }
+        Ok(Self { chassis })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ChassisDef> {
+        self.chassis.iter().find(|c| c.id == id)
+    }
+
+    /// Look up a chassis, falling back to the standard Titan frame
+    pub fn get_or_default(&self, id: &str) -> &ChassisDef {
+        self.get(id)
+            .or_else(|| self.get(DEFAULT_CHASSIS))
+            .unwrap_or(&self.chassis[0])
+    }
+
+    pub fn all(&self) -> &[ChassisDef] {
+        &self.chassis
+    }
+}
+
+/// Chassis definitions bundled with the game
+pub fn chassis_registry() -> &'static ChassisRegistry {
+    static REGISTRY: OnceLock<ChassisRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        ChassisRegistry::from_ron(include_str!("../../../assets/data/mech_chassis.ron"))
+            .expect("Invalid mech chassis data")
+    })
+}
@@ -0,0 +1,280 @@
+use glam::Vec2;
+use hecs::{Entity, World};
+use rand::Rng;
+use crate::engine::core::{Color, Transform};
+use crate::engine::physics::{CharacterBody, RigidBody};
+use crate::engine::ui::CompanionMarker;
+use crate::game::{feet_cell, find_path, Level, TILE_SIZE};
+use crate::systems::enemy::Enemy;
+use crate::systems::mech::{EnergyAllocation, Mech, MechController, MechShot, MechTransition, MechWeaponInventory, PowerChannel};
+use log::info;
+
+/// Random aim error (radians) on every autonomous shot - a pilot is still the better gunner
+pub const COMPANION_AIM_SPREAD: f32 = 0.12;
+/// How close enemies must be before an unmanned mech opens fire
+pub const COMPANION_ENGAGE_RANGE: f32 = 700.0;
+/// Radius an unmanned mech patrols when guarding an area
+pub const COMPANION_GUARD_RADIUS: f32 = 400.0;
+/// Horizontal gap kept from the pilot while following
+const FOLLOW_DISTANCE: f32 = 180.0;
+/// How close to a waypoint counts as reaching it
+const ARRIVE_DISTANCE: f32 = 24.0;
+/// Seconds between path recalculations
+const REPATH_INTERVAL: f32 = 0.5;
+/// Energy kept in reserve before the secondary weapon is used
+const SECONDARY_ENERGY_RESERVE: f32 = 0.5;
+
+/// Orders given to a mech while nobody is piloting it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CompanionCommand {
+    /// Trail the pilot on foot
+    Follow,
+    /// Stay put at a position
+    HoldPosition(Vec2),
+    /// Defend an area, chasing off enemies that come inside it
+    Guard { center: Vec2, radius: f32 },
+    /// Hunt down a specific enemy
+    Attack(Entity),
+}
+
+impl CompanionCommand {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CompanionCommand::Follow => "FOLLOW",
+            CompanionCommand::HoldPosition(_) => "HOLD",
+            CompanionCommand::Guard { .. } => "GUARD",
+            CompanionCommand::Attack(_) => "ATTACK",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            CompanionCommand::Follow => Color::new(100, 150, 255, 255),
+            CompanionCommand::HoldPosition(_) => Color::new(200, 200, 200, 255),
+            CompanionCommand::Guard { .. } => Color::new(255, 220, 80, 255),
+            CompanionCommand::Attack(_) => Color::new(255, 120, 40, 255),
+        }
+    }
+}
+
+/// Autonomous behaviour for a mech, active only while it is unoccupied
+#[derive(Clone, Debug)]
+pub struct MechCompanion {
+    pub command: CompanionCommand,
+    pub path: Vec<Vec2>,
+    pub path_goal: Option<Vec2>,
+    pub repath_timer: f32,
+    pub aim_spread: f32,
+    pub engage_range: f32,
+}
+
+impl MechCompanion {
+    pub fn new(position: Vec2) -> Self {
+        Self {
+            command: CompanionCommand::HoldPosition(position),
+            path: Vec::new(),
+            path_goal: None,
+            repath_timer: 0.0,
+            aim_spread: COMPANION_AIM_SPREAD,
+            engage_range: COMPANION_ENGAGE_RANGE,
+        }
+    }
+
+    pub fn set_command(&mut self, command: CompanionCommand) {
+        self.command = command;
+        self.path.clear();
+        self.path_goal = None;
+        self.repath_timer = 0.0;
+    }
+}
+
+/// Give a new order to a mech's companion AI
+pub fn issue_companion_command(world: &mut World, mech_entity: Entity, command: CompanionCommand) {
+    if let Ok(mut companion) = world.get::<&mut MechCompanion>(mech_entity) {
+        companion.set_command(command);
+        info!("Mech command: {}", command.name());
+    }
+}
+
+/// Living enemy closest to a point, within a radius
+pub fn find_enemy_near(world: &World, position: Vec2, radius: f32) -> Option<Entity> {
+    world
+        .query::<(&Enemy, &Transform)>()
+        .iter()
+        .filter(|(_, (enemy, _))| enemy.health > 0.0)
+        .map(|(entity, (_, transform))| (entity, transform.position.distance(position)))
+        .filter(|(_, distance)| *distance <= radius)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+}
+
+/// Minimap markers for every unmanned mech and the point its command refers to
+pub fn companion_markers(world: &World) -> Vec<CompanionMarker> {
+    let mut markers = Vec::new();
+    for (_entity, (mech, companion, transform)) in world.query::<(&Mech, &MechCompanion, &Transform)>().iter() {
+        if mech.is_occupied {
+            continue;
+        }
+        let anchor = match companion.command {
+            CompanionCommand::Follow => None,
+            CompanionCommand::HoldPosition(position) => Some(position),
+            CompanionCommand::Guard { center, .. } => Some(center),
+            CompanionCommand::Attack(target) => world.get::<&Transform>(target).ok().map(|t| t.position),
+        };
+        markers.push(CompanionMarker {
+            position: transform.position,
+            anchor,
+            color: companion.command.color(),
+            label: companion.command.name(),
+        });
+    }
+    markers
+}
+
+/// Drives unoccupied mechs according to their command and returns the shots they fire
+pub fn mech_companion_system(
+    world: &mut World,
+    level: &Level,
+    pilot_position: Option<Vec2>,
+    delta_time: f32,
+) -> Vec<MechShot> {
+    let mut shots = Vec::new();
+    let mut rng = rand::thread_rng();
+
+    let enemies: Vec<(Entity, Vec2)> = world
+        .query::<(&Enemy, &Transform)>()
+        .iter()
+        .filter(|(_, (enemy, _))| enemy.health > 0.0)
+        .map(|(entity, (_, transform))| (entity, transform.position))
+        .collect();
+    let enemy_position = |target: Entity| enemies.iter().find(|(e, _)| *e == target).map(|(_, p)| *p);
+
+    for (_entity, (mech, companion, controller, weapons, transform, body, character, allocation)) in world
+        .query_mut::<(&mut Mech, &mut MechCompanion, &mut MechController, &mut MechWeaponInventory, &Transform, &mut RigidBody, &mut CharacterBody, Option<&EnergyAllocation>)>()
+        .without::<&MechTransition>()
+    {
+        if mech.is_occupied {
+            continue;
+        }
+
+        controller.update(delta_time);
+        weapons.update(delta_time * allocation.map_or(1.0, |a| a.multiplier(PowerChannel::Weapons)));
+
+        // A dead target means the hunt is over - fall back to following the pilot
+        if let CompanionCommand::Attack(target) = companion.command {
+            if enemy_position(target).is_none() {
+                companion.set_command(CompanionCommand::Follow);
+            }
+        }
+
+        let position = transform.position;
+        let nearest_enemy = |center: Vec2, radius: f32| {
+            enemies
+                .iter()
+                .filter(|(_, p)| p.distance(center) <= radius)
+                .min_by(|a, b| a.1.distance(center).total_cmp(&b.1.distance(center)))
+                .copied()
+        };
+
+        // Where the command wants the mech to be, and how close counts as there
+        let (destination, stop_distance) = match companion.command {
+            CompanionCommand::Follow => (pilot_position, FOLLOW_DISTANCE),
+            CompanionCommand::HoldPosition(hold) => (Some(hold), ARRIVE_DISTANCE),
+            CompanionCommand::Guard { center, radius } => match nearest_enemy(center, radius) {
+                Some((_, intruder)) => (Some(intruder), companion.engage_range * 0.5),
+                None => (Some(center), ARRIVE_DISTANCE),
+            },
+            CompanionCommand::Attack(target) => (enemy_position(target), companion.engage_range * 0.6),
+        };
+
+        // Refresh the route when it's stale or the destination has moved on
+        companion.repath_timer -= delta_time;
+        if let Some(goal) = destination {
+            let goal_moved = companion.path_goal.is_none_or(|old| old.distance(goal) > TILE_SIZE * 2.0);
+            if companion.repath_timer <= 0.0 || goal_moved {
+                let max_jump_tiles = (mech.jump_power * mech.jump_power / (2.0 * mech.gravity) / TILE_SIZE) as usize;
+                companion.path = find_path(level, feet_cell(position, mech.size.y), feet_cell(goal, 0.0), max_jump_tiles)
+                    .unwrap_or_default();
+                companion.path_goal = Some(goal);
+                companion.repath_timer = REPATH_INTERVAL;
+            }
+        } else {
+            companion.path.clear();
+        }
+
+        // Drop waypoints we've already reached
+        let feet = position + Vec2::new(0.0, mech.size.y / 2.0);
+        while let Some(waypoint) = companion.path.first() {
+            if (waypoint.x - feet.x).abs() <= ARRIVE_DISTANCE && (waypoint.y - feet.y).abs() <= TILE_SIZE * 1.5 {
+                companion.path.remove(0);
+            } else {
+                break;
+            }
+        }
+
+        let arrived = destination.is_none_or(|goal| (goal.x - position.x).abs() <= stop_distance);
+        let on_ground = character.is_grounded();
+
+        let mut move_dir: f32 = 0.0;
+        if !arrived {
+            if let Some(waypoint) = companion.path.first() {
+                if (waypoint.x - feet.x).abs() > ARRIVE_DISTANCE / 2.0 {
+                    move_dir = (waypoint.x - feet.x).signum();
+                }
+                // Jump for ledges above us (ramps can just be walked up), drop through platforms
+                // to reach the floor below
+                let ramp_ahead = level
+                    .get_tile_at_position(Vec2::new(waypoint.x, feet.y - 1.0))
+                    .is_some_and(|tile| tile.solid && tile.shape.is_ramp());
+                if on_ground && waypoint.y < feet.y - TILE_SIZE / 2.0 && !ramp_ahead {
+                    body.velocity.y = -mech.jump_power;
+                } else if waypoint.y > feet.y + TILE_SIZE / 2.0 && (waypoint.x - feet.x).abs() <= TILE_SIZE {
+                    character.drop_through();
+                }
+            }
+        }
+        if move_dir != 0.0 {
+            controller.facing = move_dir;
+        }
+
+        let target_velocity = move_dir * mech.normal_speed;
+        let max_change = mech.acceleration * delta_time;
+        body.velocity.x += (target_velocity - body.velocity.x).clamp(-max_change, max_change);
+
+        // Pick something to shoot: the ordered target first, otherwise whatever is closest
+        let target = match companion.command {
+            CompanionCommand::Attack(target) => enemy_position(target)
+                .filter(|p| p.distance(transform.position) <= companion.engage_range),
+            _ => None,
+        }
+        .or_else(|| nearest_enemy(transform.position, companion.engage_range).map(|(_, p)| p));
+
+        if let Some(target_pos) = target {
+            let to_target = (target_pos - transform.position).normalize_or_zero();
+            if to_target.x != 0.0 {
+                controller.facing = to_target.x.signum();
+            }
+
+            let mut aim = || {
+                let error = rng.gen_range(-companion.aim_spread..companion.aim_spread);
+                Vec2::from_angle(error).rotate(to_target)
+            };
+            let muzzle = transform.position + to_target * mech.size.x / 2.0;
+
+            if weapons.can_fire_primary() && mech.use_energy(weapons.primary.energy_cost) {
+                weapons.fire_primary();
+                shots.push(MechShot { position: muzzle, direction: aim(), weapon: weapons.primary.clone() });
+            }
+            let reserve = mech.max_energy * SECONDARY_ENERGY_RESERVE;
+            if weapons.can_fire_secondary()
+                && mech.energy - weapons.secondary.energy_cost >= reserve
+                && mech.use_energy(weapons.secondary.energy_cost)
+            {
+                weapons.fire_secondary();
+                shots.push(MechShot { position: muzzle, direction: aim(), weapon: weapons.secondary.clone() });
+            }
+        }
+    }
+
+    shots
+}
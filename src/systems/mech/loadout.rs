@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use crate::systems::mech::{chassis_registry, mech_character_body, ChassisDef, Mech, MechWeaponInventory, MechWeaponType, DEFAULT_CHASSIS};
+use crate::engine::physics::{CharacterBody, Collider, RigidBody};
+use crate::engine::rendering::Sprite;
+use hecs::{Entity, World};
+
+/// Bolt-on upgrades that modify the mech's base stats
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MechModule {
+    /// Extra hull plating - more health, slightly slower
+    ArmorPlating,
+    /// Thruster upgrade - faster boost and higher jumps
+    Booster,
+    /// Auxiliary battery - larger energy pool
+    EnergyCell,
+}
+
+impl MechModule {
+    pub const ALL: [MechModule; 3] = [
+        MechModule::ArmorPlating,
+        MechModule::Booster,
+        MechModule::EnergyCell,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MechModule::ArmorPlating => "ARMOR PLATING",
+            MechModule::Booster => "BOOSTER",
+            MechModule::EnergyCell => "ENERGY CELL",
+        }
+    }
+
+    pub fn apply(&self, mech: &mut Mech) {
+        match self {
+            MechModule::ArmorPlating => {
+                mech.max_health += 150.0;
+                mech.normal_speed *= 0.9;
+                mech.boost_speed *= 0.9;
+            }
+            MechModule::Booster => {
+                mech.boost_speed *= 1.3;
+                mech.jump_power *= 1.15;
+            }
+            MechModule::EnergyCell => {
+                mech.max_energy += 100.0;
+            }
+        }
+    }
+}
+
+/// Weapon hardpoint and module configuration chosen in the hangar
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MechLoadout {
+    #[serde(default = "default_chassis")]
+    pub chassis: String,
+    pub primary: MechWeaponType,
+    pub secondary: MechWeaponType,
+    pub modules: Vec<Option<MechModule>>,
+}
+
+fn default_chassis() -> String {
+    DEFAULT_CHASSIS.to_string()
+}
+
+impl Default for MechLoadout {
+    fn default() -> Self {
+        Self::for_chassis(DEFAULT_CHASSIS)
+    }
+}
+
+impl MechLoadout {
+    /// Stock weapons and empty module slots for the given chassis
+    pub fn for_chassis(chassis_id: &str) -> Self {
+        let chassis = chassis_registry().get_or_default(chassis_id);
+        Self {
+            chassis: chassis.id.clone(),
+            primary: MechWeaponType::PlasmaCanon,
+            secondary: MechWeaponType::MissileLauncher,
+            modules: vec![None; chassis.hardpoints],
+        }
+    }
+
+    pub fn chassis_def(&self) -> &'static ChassisDef {
+        chassis_registry().get_or_default(&self.chassis)
+    }
+
+    /// Swap to another chassis, keeping as many installed modules as fit
+    pub fn set_chassis(&mut self, chassis_id: &str) {
+        let chassis = chassis_registry().get_or_default(chassis_id);
+        self.chassis = chassis.id.clone();
+        self.modules.resize(chassis.hardpoints, None);
+    }
+
+    pub fn installed_modules(&self) -> impl Iterator<Item = &MechModule> {
+        self.modules.iter().flatten()
+    }
+
+    /// Apply installed modules on top of the mech's base stats and refill it
+    pub fn apply_to(&self, mech: &mut Mech) {
+        for module in self.installed_modules() {
+            module.apply(mech);
+        }
+        mech.health = mech.max_health;
+        mech.energy = mech.max_energy;
+    }
+
+    /// Build a fresh mech with this loadout's modules installed
+    pub fn build_mech(&self) -> Mech {
+        let mut mech = Mech::from_chassis(self.chassis_def());
+        self.apply_to(&mut mech);
+        mech
+    }
+}
+
+/// Refit an existing mech with a new loadout, keeping its pilot
+pub fn refit_mech(world: &mut World, mech_entity: Entity, loadout: &MechLoadout) {
+    let refitted = if let Ok(mut mech) = world.get::<&mut Mech>(mech_entity) {
+        let mut refitted = loadout.build_mech();
+        refitted.is_occupied = mech.is_occupied;
+        refitted.pilot_entity = mech.pilot_entity;
+        *mech = refitted.clone();
+        refitted
+    } else {
+        return;
+    };
+
+    // A new chassis changes the hull size and weight. The sprite keeps its sheet texture.
+    let _ = world.insert(
+        mech_entity,
+        (
+            MechWeaponInventory::from_loadout(loadout),
+            Collider::Box { size: refitted.size },
+        ),
+    );
+    if let Ok(mut sprite) = world.get::<&mut Sprite>(mech_entity) {
+        sprite.size = refitted.size;
+        sprite.color = refitted.get_color();
+    }
+    if let Ok(mut body) = world.get::<&mut RigidBody>(mech_entity) {
+        body.mass = refitted.mass;
+    }
+    if let Ok(mut character) = world.get::<&mut CharacterBody>(mech_entity) {
+        *character = mech_character_body(&refitted);
+    }
+}
@@ -3,7 +3,9 @@ use crate::engine::core::Color;
 
 #[derive(Clone, Debug)]
 pub struct Mech {
+    pub chassis_id: String,
     pub size: Vec2,
+    pub mass: f32,
     pub health: f32,
     pub max_health: f32,
     pub energy: f32,
@@ -11,21 +13,40 @@ pub struct Mech {
     pub boost_speed: f32,
     pub normal_speed: f32,
     pub jump_power: f32,
+    pub acceleration: f32,
+    pub gravity: f32,
+    pub crushes_tiles: bool,
+    pub mobility: MechMobility,
     pub is_occupied: bool,
     pub pilot_entity: Option<hecs::Entity>,
 }
 
 impl Mech {
+    /// Active mech - bright blue/silver
+    pub const ACTIVE_COLOR: Color = Color { r: 100, g: 150, b: 255, a: 255 };
+    /// Inactive mech - darker gray
+    pub const INACTIVE_COLOR: Color = Color { r: 80, g: 80, b: 100, a: 255 };
+
     pub fn new() -> Self {
+        Self::from_chassis(chassis_registry().get_or_default(DEFAULT_CHASSIS))
+    }
+
+    pub fn from_chassis(chassis: &ChassisDef) -> Self {
         Self {
-            size: Vec2::new(80.0, 120.0), // Much larger than player
-            health: 500.0,
-            max_health: 500.0,
-            energy: 200.0,
-            max_energy: 200.0,
-            boost_speed: 800.0,
-            normal_speed: 400.0,
-            jump_power: 1200.0,
+            chassis_id: chassis.id.clone(),
+            size: chassis.size,
+            mass: chassis.mass,
+            health: chassis.health,
+            max_health: chassis.health,
+            energy: chassis.energy,
+            max_energy: chassis.energy,
+            boost_speed: chassis.boost_speed,
+            normal_speed: chassis.normal_speed,
+            jump_power: chassis.jump_power,
+            acceleration: chassis.acceleration,
+            gravity: chassis.gravity,
+            crushes_tiles: chassis.crushes_tiles,
+            mobility: chassis.mobility,
             is_occupied: false,
             pilot_entity: None,
         }
@@ -59,13 +80,22 @@ impl Mech {
 
     pub fn get_color(&self) -> Color {
         if self.is_occupied {
-            // Active mech - bright blue/silver
-            Color::new(100, 150, 255, 255)
+            Self::ACTIVE_COLOR
         } else {
-            // Inactive mech - darker gray
-            Color::new(80, 80, 100, 255)
+            Self::INACTIVE_COLOR
         }
     }
+
+    /// Color while powering up or down (0.0 = inactive, 1.0 = fully active)
+    pub fn get_power_color(&self, power: f32) -> Color {
+        Self::INACTIVE_COLOR.lerp(Self::ACTIVE_COLOR, power)
+    }
+}
+
+impl Default for Mech {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -74,6 +104,11 @@ pub struct MechController {
     pub boost_cooldown: f32,
     pub stomp_cooldown: f32,
     pub weapon_cooldown: f32,
+    pub dash_timer: f32,
+    pub dash_cooldown: f32,
+    pub dash_direction: f32,
+    pub facing: f32,
+    pub is_stomping: bool,
 }
 
 impl MechController {
@@ -83,6 +118,11 @@ impl MechController {
             boost_cooldown: 0.0,
             stomp_cooldown: 0.0,
             weapon_cooldown: 0.0,
+            dash_timer: 0.0,
+            dash_cooldown: 0.0,
+            dash_direction: 1.0,
+            facing: 1.0,
+            is_stomping: false,
         }
     }
 
@@ -90,6 +130,12 @@ impl MechController {
         self.boost_cooldown = (self.boost_cooldown - delta_time).max(0.0);
         self.stomp_cooldown = (self.stomp_cooldown - delta_time).max(0.0);
         self.weapon_cooldown = (self.weapon_cooldown - delta_time).max(0.0);
+        self.dash_timer = (self.dash_timer - delta_time).max(0.0);
+        self.dash_cooldown = (self.dash_cooldown - delta_time).max(0.0);
+    }
+
+    pub fn is_dashing(&self) -> bool {
+        self.dash_timer > 0.0
     }
 
     pub fn can_stomp(&self) -> bool {
@@ -97,14 +143,36 @@ impl MechController {
     }
 
     pub fn do_stomp(&mut self) {
-        self.stomp_cooldown = 2.0; // 2 second cooldown
+        self.stomp_cooldown = STOMP_COOLDOWN;
+        self.is_stomping = true;
+    }
+
+    /// Fraction of the stomp cooldown that has elapsed (1.0 = ready)
+    pub fn stomp_readiness(&self) -> f32 {
+        1.0 - self.stomp_cooldown / STOMP_COOLDOWN
+    }
+}
+
+impl Default for MechController {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
+pub mod chassis;
+pub mod companion;
 pub mod weapons;
+pub mod loadout;
 pub mod movement;
+pub mod power;
+pub mod stomp;
 pub mod transformation;
 
+pub use chassis::*;
+pub use companion::*;
 pub use weapons::*;
+pub use loadout::*;
 pub use movement::*;
-pub use transformation::*;
\ No newline at end of file
+pub use power::*;
+pub use stomp::*;
+pub use transformation::*;
@@ -1,34 +1,35 @@
-use glam::Vec2;
 use hecs::World;
 use crate::engine::platform::InputState;
-use crate::engine::physics::RigidBody;
-use crate::engine::core::Transform;
+use crate::engine::physics::{CharacterBody, RigidBody};
+use crate::engine::core::{Rect, Transform};
+use crate::game::level::TileType;
 use crate::game::Level;
-use crate::systems::mech::{Mech, MechController};
+use crate::systems::mech::{EnergyAllocation, Mech, MechController, MechMobility, PowerChannel, STOMP_SLAM_SPEED};
 use sdl2::keyboard::Keycode;
 
+/// Sets the velocity of piloted mechs from input and returns the destructible tiles crushed by heavy chassis
 pub fn mech_movement_system(
     world: &mut World,
     input: &InputState,
     level: &Level,
     delta_time: f32,
-) {
-    for (_entity, (mech, controller, transform, body)) in world
-        .query_mut::<(&mut Mech, &mut MechController, &mut Transform, &mut RigidBody)>()
+) -> Vec<(usize, usize)> {
+    let mut crushed_tiles = Vec::new();
+
+    for (_entity, (mech, controller, transform, body, character, allocation)) in world
+        .query_mut::<(&mut Mech, &mut MechController, &Transform, &mut RigidBody, &mut CharacterBody, Option<&EnergyAllocation>)>()
     {
         if !mech.is_occupied {
-            continue; // Don't move unmanned mechs
+            continue; // Unmanned mechs are driven by their companion AI
         }
 
         controller.update(delta_time);
-        
-        // Regenerate energy when not boosting
-        if !controller.is_boosting {
-            mech.regenerate_energy(30.0, delta_time); // 30 energy per second
-        }
+
+        // Mobility power makes boosting and dashing cheaper
+        let mobility = allocation.map_or(1.0, |a| a.multiplier(PowerChannel::Mobility));
 
         // Horizontal movement
-        let mut move_dir = 0.0;
+        let mut move_dir: f32 = 0.0;
         if input.is_key_down(Keycode::A) || input.is_key_down(Keycode::Left) {
             move_dir = -1.0;
         }
@@ -36,6 +37,10 @@ pub fn mech_movement_system(
             move_dir = 1.0;
         }
 
+        if move_dir != 0.0 {
+            controller.facing = move_dir;
+        }
+
         // Check for boost (Shift key)
         controller.is_boosting = false;
         let mut speed = mech.normal_speed;
@@ -44,62 +49,63 @@ pub fn mech_movement_system(
             if mech.can_boost() && move_dir != 0.0 {
                 controller.is_boosting = true;
                 speed = mech.boost_speed;
-                mech.use_energy(20.0 * delta_time); // Consume 20 energy per second while boosting
+                mech.use_energy(20.0 * delta_time / mobility); // Consume 20 energy per second while boosting
             }
         }
 
-        body.velocity.x = move_dir * speed;
+        // Light chassis dash: tapping boost fires a short burst in the facing direction
+        if let MechMobility::Dash { speed: dash_speed, duration, cooldown, energy_cost } = mech.mobility {
+            let dash_pressed = input.is_key_pressed(Keycode::LShift) || input.is_key_pressed(Keycode::RShift);
+            if dash_pressed && controller.dash_cooldown <= 0.0 && mech.use_energy(energy_cost / mobility) {
+                controller.dash_timer = duration;
+                controller.dash_cooldown = cooldown;
+                controller.dash_direction = controller.facing;
+            }
+            if controller.is_dashing() {
+                speed = dash_speed;
+                move_dir = controller.dash_direction;
+            }
+        }
 
-        // Check ground collision for jumping
-        let ground_check_pos = Vec2::new(
-            transform.position.x,
-            transform.position.y + mech.size.y / 2.0 + 5.0,
-        );
-        
-        let tile_x = (ground_check_pos.x / crate::game::TILE_SIZE) as usize;
-        let tile_y = (ground_check_pos.y / crate::game::TILE_SIZE) as usize;
-        
-        let on_ground = if let Some(tile) = level.get_tile(tile_x, tile_y) {
-            tile.tile_type != crate::game::level::TileType::Empty
+        // Accelerate towards the target speed - heavier chassis take longer to get going
+        let target_velocity = move_dir * speed;
+        let max_change = mech.acceleration * delta_time;
+        if controller.is_dashing() {
+            body.velocity.x = target_velocity;
         } else {
-            false
-        };
+            body.velocity.x += (target_velocity - body.velocity.x).clamp(-max_change, max_change);
+        }
 
-        // Jumping with more power than regular player
+        let on_ground = character.is_grounded();
+        let down_held = input.is_key_down(Keycode::S) || input.is_key_down(Keycode::Down);
+
+        // Jumping with more power than regular player, or Down + Jump to drop off a platform
         if on_ground && (input.is_key_pressed(Keycode::W) || 
                         input.is_key_pressed(Keycode::Up) || 
-                        input.is_key_pressed(Keycode::Space)) {
+                        input.is_key_pressed(Keycode::Space)) &&
+           !(down_held && character.drop_through()) {
             body.velocity.y = -mech.jump_power;
         }
 
-        // Gravity (mechs are heavier, fall faster)
-        if !on_ground {
-            body.velocity.y += 2000.0 * delta_time; // Higher gravity for mechs
-        } else if body.velocity.y > 0.0 {
-            body.velocity.y = 0.0;
-        }
-
         // Ground stomp ability (Down + Jump while in air)
         if !on_ground && controller.can_stomp() {
-            if (input.is_key_down(Keycode::S) || input.is_key_down(Keycode::Down)) &&
-               input.is_key_pressed(Keycode::Space) {
-                body.velocity.y = 1500.0; // Fast downward slam
+            if down_held && input.is_key_pressed(Keycode::Space) {
+                body.velocity.y = STOMP_SLAM_SPEED; // Fast downward slam
                 controller.do_stomp();
             }
         }
 
-        // Apply movement
-        transform.position += body.velocity * delta_time;
-
-        // Keep mech in bounds
-        transform.position.x = transform.position.x.max(mech.size.x / 2.0);
-        transform.position.x = transform.position.x.min(level.width as f32 * crate::game::TILE_SIZE - mech.size.x / 2.0);
-        
-        // Prevent falling through the world
-        let ground_y = (level.height - 3) as f32 * crate::game::TILE_SIZE - mech.size.y / 2.0;
-        if transform.position.y > ground_y {
-            transform.position.y = ground_y;
-            body.velocity.y = 0.0;
+        // Heavy chassis flatten destructible tiles they walk into
+        if mech.crushes_tiles && on_ground && move_dir != 0.0 {
+            let reach = Rect::new(
+                transform.position.x - mech.size.x / 2.0 + move_dir * 8.0,
+                transform.position.y - mech.size.y / 2.0,
+                mech.size.x,
+                mech.size.y,
+            );
+            crushed_tiles.extend(level.tiles_of_type_in_rect(reach, TileType::Destructible));
         }
     }
-}
\ No newline at end of file
+
+    crushed_tiles
+}
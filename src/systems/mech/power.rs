@@ -0,0 +1,267 @@
+use glam::Vec2;
+use hecs::{Entity, World};
+use sdl2::keyboard::Keycode;
+use sdl2::mouse::MouseButton;
+use crate::engine::core::{Color, Transform};
+use crate::engine::platform::InputState;
+use crate::engine::rendering::{Camera, RenderLayer, RenderQueue, Sprite};
+use crate::systems::mech::{Mech, MechController};
+
+/// Reactor output in energy per second with the default power split
+pub const BASE_ENERGY_REGEN: f32 = 30.0;
+/// Most pips a single channel can hold
+pub const MAX_CHANNEL_PIPS: u32 = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PowerChannel {
+    Weapons,
+    Shields,
+    Mobility,
+}
+
+impl PowerChannel {
+    pub const ALL: [PowerChannel; 3] = [PowerChannel::Weapons, PowerChannel::Shields, PowerChannel::Mobility];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PowerChannel::Weapons => "WPN",
+            PowerChannel::Shields => "SHD",
+            PowerChannel::Mobility => "MOB",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            PowerChannel::Weapons => Color::new(255, 120, 60, 255),
+            PowerChannel::Shields => Color::new(80, 200, 255, 255),
+            PowerChannel::Mobility => Color::new(120, 255, 120, 255),
+        }
+    }
+}
+
+/// How the reactor is split between weapons, shields and mobility.
+/// Weapons pips speed up weapon cycling, shield pips speed up energy regen and make
+/// absorbed hits cheaper, mobility pips make boosting and dashing cheaper.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnergyAllocation {
+    pub weapons: u32,
+    pub shields: u32,
+    pub mobility: u32,
+}
+
+impl EnergyAllocation {
+    /// Balanced split of the six available pips
+    pub fn new() -> Self {
+        Self {
+            weapons: 2,
+            shields: 2,
+            mobility: 2,
+        }
+    }
+
+    pub fn pips(&self, channel: PowerChannel) -> u32 {
+        match channel {
+            PowerChannel::Weapons => self.weapons,
+            PowerChannel::Shields => self.shields,
+            PowerChannel::Mobility => self.mobility,
+        }
+    }
+
+    fn pips_mut(&mut self, channel: PowerChannel) -> &mut u32 {
+        match channel {
+            PowerChannel::Weapons => &mut self.weapons,
+            PowerChannel::Shields => &mut self.shields,
+            PowerChannel::Mobility => &mut self.mobility,
+        }
+    }
+
+    /// Move one pip into a channel, taken from whichever other channel has the most
+    pub fn shift_to(&mut self, channel: PowerChannel) {
+        if self.pips(channel) >= MAX_CHANNEL_PIPS {
+            return;
+        }
+
+        let donor = PowerChannel::ALL
+            .iter()
+            .copied()
+            .filter(|other| *other != channel && self.pips(*other) > 0)
+            .max_by_key(|other| self.pips(*other));
+
+        if let Some(donor) = donor {
+            *self.pips_mut(donor) -= 1;
+            *self.pips_mut(channel) += 1;
+        }
+    }
+
+    /// Effect multiplier for a channel: 0.5 with no pips, 1.0 balanced, 1.5 maxed out
+    pub fn multiplier(&self, channel: PowerChannel) -> f32 {
+        0.5 + 0.25 * self.pips(channel) as f32
+    }
+
+    pub fn regen_rate(&self) -> f32 {
+        BASE_ENERGY_REGEN * self.multiplier(PowerChannel::Shields)
+    }
+}
+
+impl Default for EnergyAllocation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Directional energy barrier raised by the pilot towards the cursor
+#[derive(Clone, Debug)]
+pub struct MechShield {
+    pub is_raised: bool,
+    pub direction: Vec2,
+    pub half_arc: f32,
+    pub energy_per_damage: f32,
+    pub regen_delay: f32,
+    pub regen_timer: f32,
+    pub overheat_duration: f32,
+    pub overheat_timer: f32,
+    pub hit_flash: f32,
+}
+
+impl MechShield {
+    pub fn new() -> Self {
+        Self {
+            is_raised: false,
+            direction: Vec2::new(1.0, 0.0),
+            half_arc: 0.9,          // Roughly a 100 degree cone
+            energy_per_damage: 0.8,
+            regen_delay: 1.5,       // Seconds of no regen after taking a hit
+            regen_timer: 0.0,
+            overheat_duration: 3.0, // Seconds the emitter needs to cool down after breaking
+            overheat_timer: 0.0,
+            hit_flash: 0.0,
+        }
+    }
+
+    pub fn is_overheated(&self) -> bool {
+        self.overheat_timer > 0.0
+    }
+
+    pub fn can_regenerate(&self) -> bool {
+        !self.is_raised && self.regen_timer <= 0.0
+    }
+
+    /// Whether a projectile travelling along `incoming` hits the front of the shield
+    pub fn covers(&self, incoming: Vec2) -> bool {
+        let towards_source = -incoming.normalize_or_zero();
+        self.is_raised && towards_source.dot(self.direction) >= self.half_arc.cos()
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        self.regen_timer = (self.regen_timer - delta_time).max(0.0);
+        self.overheat_timer = (self.overheat_timer - delta_time).max(0.0);
+        self.hit_flash = (self.hit_flash - delta_time).max(0.0);
+    }
+}
+
+impl Default for MechShield {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Apply a hit to a mech, letting a raised shield soak it up first.
+/// Returns true when the shield absorbed the whole hit.
+pub fn apply_mech_damage(world: &mut World, mech_entity: Entity, damage: f32, incoming: Vec2) -> bool {
+    let Ok((mech, shield, allocation)) =
+        world.query_one_mut::<(&mut Mech, Option<&mut MechShield>, Option<&EnergyAllocation>)>(mech_entity)
+    else {
+        return false;
+    };
+    let shield_multiplier = allocation.map_or(1.0, |a| a.multiplier(PowerChannel::Shields));
+
+    let mut remaining = damage;
+    if let Some(shield) = shield {
+        shield.regen_timer = shield.regen_delay / shield_multiplier;
+
+        if shield.covers(incoming) {
+            shield.hit_flash = 0.15;
+            let cost = damage * shield.energy_per_damage / shield_multiplier;
+            if mech.use_energy(cost) {
+                return true;
+            }
+
+            // Not enough energy to stop it all - the emitter burns out and the rest gets through
+            let absorbed = mech.energy / cost * damage;
+            mech.energy = 0.0;
+            remaining -= absorbed;
+            shield.is_raised = false;
+            shield.overheat_timer = shield.overheat_duration;
+        }
+    }
+
+    mech.take_damage(remaining);
+    false
+}
+
+/// Handles the power split hotkeys, shield raising and energy regeneration for all mechs
+pub fn mech_power_system(world: &mut World, input: &InputState, camera: &Camera, delta_time: f32) {
+    let cursor = camera.screen_to_world(input.mouse_position());
+
+    for (_entity, (mech, controller, shield, allocation, transform)) in world
+        .query_mut::<(&mut Mech, &MechController, &mut MechShield, &mut EnergyAllocation, &Transform)>()
+    {
+        shield.update(delta_time);
+
+        if mech.is_occupied {
+            // 1/2/3 shift a pip into weapons/shields/mobility, 4 rebalances
+            if input.is_key_pressed(Keycode::Num1) {
+                allocation.shift_to(PowerChannel::Weapons);
+            }
+            if input.is_key_pressed(Keycode::Num2) {
+                allocation.shift_to(PowerChannel::Shields);
+            }
+            if input.is_key_pressed(Keycode::Num3) {
+                allocation.shift_to(PowerChannel::Mobility);
+            }
+            if input.is_key_pressed(Keycode::Num4) {
+                *allocation = EnergyAllocation::new();
+            }
+
+            // Hold right mouse to raise the shield towards the cursor
+            shield.is_raised = input.is_mouse_button_down(MouseButton::Right)
+                && !shield.is_overheated()
+                && mech.energy > 0.0;
+            let aim = (cursor - transform.position).normalize_or_zero();
+            if aim != Vec2::ZERO {
+                shield.direction = aim;
+            }
+        } else {
+            shield.is_raised = false;
+        }
+
+        if shield.can_regenerate() && !controller.is_boosting {
+            mech.regenerate_energy(allocation.regen_rate(), delta_time);
+        }
+    }
+}
+
+/// Draw raised shields as an arc of segments in front of each mech
+pub fn draw_mech_shields(queue: &mut RenderQueue, world: &World) {
+    for (_entity, (mech, shield, transform)) in world.query::<(&Mech, &MechShield, &Transform)>().iter() {
+        if !shield.is_raised {
+            continue;
+        }
+
+        let radius = mech.size.max_element() / 2.0 + 16.0;
+        let strength = if mech.max_energy > 0.0 { mech.energy / mech.max_energy } else { 0.0 };
+        let color = if shield.hit_flash > 0.0 {
+            Color::new(255, 255, 255, 230)
+        } else {
+            Color::new(80, 200, 255, (80.0 + 150.0 * strength) as u8)
+        };
+
+        let segments = 12;
+        let base_angle = shield.direction.y.atan2(shield.direction.x);
+        for i in 0..=segments {
+            let angle = base_angle - shield.half_arc + 2.0 * shield.half_arc * i as f32 / segments as f32;
+            let position = transform.position + Vec2::from_angle(angle) * radius;
+            queue.submit(RenderLayer::Effects, Sprite::new(Vec2::splat(8.0), color), Transform::new(position));
+        }
+    }
+}
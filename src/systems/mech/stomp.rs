@@ -0,0 +1,168 @@
+use glam::Vec2;
+use hecs::World;
+use crate::engine::core::{Color, Rect, Transform};
+use crate::engine::physics::{CharacterBody, CollisionLayers, RigidBody, SpatialHash};
+use crate::engine::rendering::{RenderLayer, RenderQueue, Sprite};
+use crate::game::level::TileType;
+use crate::game::{Level, TILE_SIZE};
+use crate::systems::enemy::Enemy;
+use crate::systems::mech::{Mech, MechController};
+use crate::systems::particles::{Particle, ParticleSystem};
+
+/// Seconds between stomps
+pub const STOMP_COOLDOWN: f32 = 2.0;
+/// Downward speed the mech slams down with
+pub const STOMP_SLAM_SPEED: f32 = 1500.0;
+/// Shockwave radius at full strength
+const STOMP_RADIUS: f32 = 220.0;
+/// Damage dealt at the centre of a full strength shockwave
+const STOMP_DAMAGE: f32 = 80.0;
+/// Outward push at the centre of a full strength shockwave
+const STOMP_KNOCKBACK: f32 = 700.0;
+/// Enemies up to this tall get knocked off their feet
+const STOMP_STUN_MAX_HEIGHT: f32 = 64.0;
+/// Stun duration at full strength
+const STOMP_STUN_DURATION: f32 = 1.5;
+
+/// A stomping mech hitting the ground
+#[derive(Clone, Debug)]
+pub struct StompImpact {
+    pub position: Vec2,
+    pub fall_speed: f32,
+}
+
+impl StompImpact {
+    /// 1.0 for a slam from a short hop, more for longer falls
+    pub fn strength(&self) -> f32 {
+        stomp_strength(self.fall_speed)
+    }
+
+    pub fn radius(&self) -> f32 {
+        stomp_radius(self.fall_speed)
+    }
+}
+
+fn stomp_strength(fall_speed: f32) -> f32 {
+    (fall_speed / STOMP_SLAM_SPEED).clamp(0.25, 1.5)
+}
+
+fn stomp_radius(fall_speed: f32) -> f32 {
+    STOMP_RADIUS * (0.5 + 0.5 * stomp_strength(fall_speed))
+}
+
+/// Everything the caller has to apply outside the ECS after a stomp lands
+pub struct StompOutcome {
+    pub crushed_tiles: Vec<(usize, usize)>,
+    pub particles: Vec<Particle>,
+    /// Screen shake trauma to add, 0-1
+    pub trauma: f32,
+}
+
+/// Stomping mechs that touched down during the last physics step
+pub fn stomp_landing_system(world: &mut World) -> Vec<StompImpact> {
+    let mut impacts = Vec::new();
+
+    for (_entity, (mech, controller, transform, character)) in world
+        .query_mut::<(&Mech, &mut MechController, &Transform, &CharacterBody)>()
+    {
+        if controller.is_stomping && character.contacts.just_landed {
+            controller.is_stomping = false;
+            impacts.push(StompImpact {
+                position: transform.position + Vec2::new(0.0, mech.size.y / 2.0),
+                fall_speed: character.contacts.landing_speed,
+            });
+        }
+    }
+
+    impacts
+}
+
+/// Hurt, shove and stun enemies caught in the shockwave and collect the tiles it breaks
+pub fn resolve_stomp(world: &mut World, level: &Level, broadphase: &SpatialHash, impact: &StompImpact) -> StompOutcome {
+    let strength = impact.strength();
+    let radius = impact.radius();
+
+    for entity in broadphase.query_circle(impact.position, radius, CollisionLayers::ENEMY) {
+        let Ok((enemy, transform, body)) = world.query_one_mut::<(&mut Enemy, &Transform, &mut RigidBody)>(entity) else {
+            continue;
+        };
+        let offset = transform.position - impact.position;
+        let distance = offset.length();
+
+        // Anything the blast touches takes at least a glancing hit
+        let falloff = (1.0 - distance / radius).max(0.1);
+        enemy.health = (enemy.health - STOMP_DAMAGE * strength * falloff).max(0.0);
+        enemy.show_health_bar();
+
+        let outward = if offset.x.abs() > 0.1 { offset.x.signum() } else { 1.0 };
+        body.velocity.x = outward * STOMP_KNOCKBACK * strength * falloff;
+        body.velocity.y = -STOMP_KNOCKBACK * 0.5 * strength * falloff;
+
+        if enemy.size.y <= STOMP_STUN_MAX_HEIGHT {
+            enemy.stun(STOMP_STUN_DURATION * strength);
+        }
+    }
+
+    // Destructible tiles within the blast circle give way
+    let blast = Rect::new(impact.position.x - radius, impact.position.y - radius, radius * 2.0, radius * 2.0);
+    let crushed_tiles = level
+        .tiles_of_type_in_rect(blast, TileType::Destructible)
+        .into_iter()
+        .filter(|&(x, y)| {
+            let center = Vec2::new((x as f32 + 0.5) * TILE_SIZE, (y as f32 + 0.5) * TILE_SIZE);
+            center.distance(impact.position) <= radius
+        })
+        .collect();
+
+    let dust_color = level
+        .get_tile_at_position(impact.position + Vec2::new(0.0, TILE_SIZE / 2.0))
+        .map(|tile| tile.color)
+        .unwrap_or(Color::new(120, 100, 80, 255));
+    let mut particles = ParticleSystem::create_shockwave_particles(impact.position, radius, (24.0 * strength) as u32);
+    particles.extend(ParticleSystem::create_debris_particles(impact.position, dust_color, (10.0 * strength) as u32));
+
+    StompOutcome {
+        crushed_tiles,
+        particles,
+        trauma: 0.4 + 0.5 * strength,
+    }
+}
+
+/// Row of the first solid tile below a point, as a world y coordinate
+fn ground_below(level: &Level, position: Vec2) -> Option<f32> {
+    let x = (position.x / TILE_SIZE).max(0.0) as usize;
+    let start_y = (position.y / TILE_SIZE).max(0.0) as usize;
+    (start_y..level.height)
+        .find(|&y| level.get_tile(x, y).is_some_and(|tile| tile.solid || tile.one_way))
+        .map(|y| y as f32 * TILE_SIZE)
+}
+
+/// Warn where a falling stomp will land: a pulsing band on the ground the width of the blast
+pub fn draw_stomp_telegraphs(queue: &mut RenderQueue, world: &World, level: &Level) {
+    for (_entity, (mech, controller, transform, body)) in world
+        .query::<(&Mech, &MechController, &Transform, &RigidBody)>()
+        .iter()
+    {
+        if !controller.is_stomping {
+            continue;
+        }
+
+        let feet = transform.position + Vec2::new(0.0, mech.size.y / 2.0);
+        let Some(ground_y) = ground_below(level, feet) else {
+            continue;
+        };
+
+        let radius = stomp_radius(body.velocity.y);
+        let pulse = 0.5 + 0.5 * (controller.stomp_cooldown * 20.0).sin();
+        let color = Color::new(255, 120, 40, (90.0 + 110.0 * pulse) as u8);
+
+        let band = Sprite::new(Vec2::new(radius * 2.0, 6.0), color);
+        queue.submit(RenderLayer::Effects, band, Transform::new(Vec2::new(feet.x, ground_y - 3.0)));
+
+        // Edge posts mark how far the shockwave reaches
+        let post = Sprite::new(Vec2::new(6.0, 24.0), color);
+        for side in [-1.0, 1.0] {
+            queue.submit(RenderLayer::Effects, post.clone(), Transform::new(Vec2::new(feet.x + side * radius, ground_y - 12.0)));
+        }
+    }
+}
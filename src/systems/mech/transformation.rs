@@ -1,10 +1,12 @@
 use glam::Vec2;
 use hecs::{World, Entity};
 use crate::engine::core::{Transform, Color};
-use crate::engine::physics::{RigidBody, Collider};
-use crate::engine::rendering::Sprite;
+use crate::engine::physics::{CharacterBody, RigidBody, Collider, CollisionFilter};
+use crate::engine::rendering::{Animator, Sprite};
 use crate::systems::player::Player;
-use crate::systems::mech::{Mech, MechController, MechWeaponInventory};
+use crate::systems::mech::{EnergyAllocation, Mech, MechCompanion, MechController, MechLoadout, MechShield, MechWeaponInventory};
+use crate::systems::particles::{ParticleSystem, spawn_particle};
+use crate::systems::lighting_system::mech_headlight;
 use log::info;
 
 pub struct TransformationResult {
@@ -103,11 +105,14 @@ pub fn exit_mech(
         }
     }
 
-    // Restore player components
-    let _ = world.insert_one(
-        pilot_entity,
-        Sprite::new(Vec2::new(24.0, 40.0), Color::new(57, 255, 20, 255)),
-    );
+    // Restore player components, back on the pilot's sprite sheet if it has one
+    let mut sprite = Sprite::new(Vec2::new(24.0, 40.0), Color::new(57, 255, 20, 255));
+    if let Ok(animator) = world.get::<&Animator>(pilot_entity) {
+        if let Some(sheet) = &animator.sheet {
+            sheet.apply(&mut sprite);
+        }
+    }
+    let _ = world.insert_one(pilot_entity, sprite);
     let _ = world.insert_one(
         pilot_entity,
         Collider::Box {
@@ -128,6 +133,17 @@ pub fn exit_mech(
     }
 }
 
+/// Throw the pilot out on the spot, cancelling any embark or disembark under way
+pub fn eject_pilot(world: &mut World, mech_entity: Entity) -> TransformationResult {
+    let _ = world.remove_one::<MechTransition>(mech_entity);
+    let pilot_entity = world.get::<&Mech>(mech_entity).ok().and_then(|mech| mech.pilot_entity);
+    let result = exit_mech(world, mech_entity);
+    if let Some(pilot_entity) = pilot_entity {
+        set_pilot_physics(world, pilot_entity, true);
+    }
+    result
+}
+
 pub fn find_nearest_mech(world: &World, player_pos: Vec2, max_distance: f32) -> Option<Entity> {
     let mut nearest_mech = None;
     let mut nearest_distance = max_distance;
@@ -147,18 +163,413 @@ pub fn find_nearest_mech(world: &World, player_pos: Vec2, max_distance: f32) ->
     nearest_mech
 }
 
-pub fn spawn_mech(world: &mut World, position: Vec2) -> Entity {
-    let mech = Mech::new();
+pub fn spawn_mech(world: &mut World, position: Vec2, chassis_id: &str) -> Entity {
+    spawn_mech_with_loadout(world, position, &MechLoadout::for_chassis(chassis_id))
+}
+
+pub fn spawn_mech_with_loadout(world: &mut World, position: Vec2, loadout: &MechLoadout) -> Entity {
+    let mech = loadout.build_mech();
     let color = mech.get_color();
     let size = mech.size;
+    let mass = mech.mass;
+    let character = mech_character_body(&mech);
+    let headlight = mech_headlight(&mech);
     
     world.spawn((
         mech,
         MechController::new(),
-        MechWeaponInventory::new(),
+        MechWeaponInventory::from_loadout(loadout),
+        MechCompanion::new(position),
+        MechShield::new(),
+        EnergyAllocation::new(),
         Transform::new(position),
         Sprite::new(size, color),
-        RigidBody::new(10.0), // Mechs are heavy
+        RigidBody::new(mass), // Mechs are heavy
         Collider::Box { size },
+        CollisionFilter::mech(),
+        character,
+        headlight,
     ))
-}
\ No newline at end of file
+}
+
+/// Physics body for a mech - heavy chassis fall fast and need room for stomp slams
+pub fn mech_character_body(mech: &Mech) -> CharacterBody {
+    CharacterBody::new(mech.size, mech.gravity).with_terminal_velocity(2400.0)
+}
+
+/// Turn physics on or off for a pilot climbing in or out (their position is scripted meanwhile)
+fn set_pilot_physics(world: &mut World, pilot_entity: Entity, enabled: bool) {
+    if let Ok(mut character) = world.get::<&mut CharacterBody>(pilot_entity) {
+        character.enabled = enabled;
+        character.contacts = Default::default();
+    }
+}
+
+/// Camera zoom while on foot
+pub const ON_FOOT_ZOOM: f32 = 1.0;
+/// Camera zoom while piloting a mech
+pub const MECH_ZOOM: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransitionKind {
+    Embark,
+    Disembark,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransitionPhase {
+    /// Pilot climbs between the ground and the cockpit hatch
+    Climb,
+    /// Mech reactor spins up (embark) or down (disembark)
+    Power,
+}
+
+/// Timed embark/disembark sequence, attached to the mech being entered or exited
+#[derive(Clone, Debug)]
+pub struct MechTransition {
+    pub kind: TransitionKind,
+    pub phase: TransitionPhase,
+    pub pilot_entity: Entity,
+    pub phase_timer: f32,
+    pub climb_duration: f32,
+    pub power_duration: f32,
+    /// Where the pilot starts (embark) or ends up (disembark) on the ground
+    pub ground_position: Vec2,
+    pub pilot_health: f32,
+    pub mech_health: f32,
+    particle_timer: f32,
+}
+
+impl MechTransition {
+    pub fn new(kind: TransitionKind, pilot_entity: Entity, ground_position: Vec2, pilot_health: f32, mech_health: f32) -> Self {
+        // Embarking starts with the climb, disembarking starts by powering down
+        let phase = match kind {
+            TransitionKind::Embark => TransitionPhase::Climb,
+            TransitionKind::Disembark => TransitionPhase::Power,
+        };
+
+        Self {
+            kind,
+            phase,
+            pilot_entity,
+            phase_timer: 0.0,
+            climb_duration: 0.6,
+            power_duration: 0.9,
+            ground_position,
+            pilot_health,
+            mech_health,
+            particle_timer: 0.0,
+        }
+    }
+
+    fn phase_duration(&self) -> f32 {
+        match self.phase {
+            TransitionPhase::Climb => self.climb_duration,
+            TransitionPhase::Power => self.power_duration,
+        }
+    }
+
+    pub fn phase_progress(&self) -> f32 {
+        (self.phase_timer / self.phase_duration()).clamp(0.0, 1.0)
+    }
+
+    /// Progress through the whole sequence (0.0 to 1.0)
+    pub fn progress(&self) -> f32 {
+        let total = self.climb_duration + self.power_duration;
+        let first_phase = match self.kind {
+            TransitionKind::Embark => TransitionPhase::Climb,
+            TransitionKind::Disembark => TransitionPhase::Power,
+        };
+        let done = if self.phase == first_phase {
+            0.0
+        } else {
+            match first_phase {
+                TransitionPhase::Climb => self.climb_duration,
+                TransitionPhase::Power => self.power_duration,
+            }
+        };
+        ((done + self.phase_timer.min(self.phase_duration())) / total).clamp(0.0, 1.0)
+    }
+
+    /// Reactor power level used for the hull color ramp (0.0 = cold, 1.0 = online)
+    pub fn power_level(&self) -> f32 {
+        match (self.kind, self.phase) {
+            (TransitionKind::Embark, TransitionPhase::Climb) => 0.0,
+            (TransitionKind::Embark, TransitionPhase::Power) => self.phase_progress(),
+            (TransitionKind::Disembark, TransitionPhase::Power) => 1.0 - self.phase_progress(),
+            (TransitionKind::Disembark, TransitionPhase::Climb) => 0.0,
+        }
+    }
+
+    /// Camera zoom blended between on-foot and mech views
+    pub fn camera_zoom(&self) -> f32 {
+        let t = self.progress();
+        let t = t * t * (3.0 - 2.0 * t); // Smooth step
+        match self.kind {
+            TransitionKind::Embark => ON_FOOT_ZOOM + (MECH_ZOOM - ON_FOOT_ZOOM) * t,
+            TransitionKind::Disembark => MECH_ZOOM + (ON_FOOT_ZOOM - MECH_ZOOM) * t,
+        }
+    }
+}
+
+/// Outcome of a finished (or aborted) transition
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransitionEvent {
+    Embarked { mech: Entity },
+    Disembarked { mech: Entity },
+    /// Damage interrupted the sequence; `pilot_inside` tells which mode we ended up in
+    Interrupted { mech: Entity, pilot_inside: bool },
+}
+
+/// Cockpit hatch position on top of the mech hull
+pub fn hatch_position(mech_pos: Vec2, mech_size: Vec2) -> Vec2 {
+    Vec2::new(mech_pos.x, mech_pos.y - mech_size.y / 2.0 + 10.0)
+}
+
+pub fn begin_embark(
+    world: &mut World,
+    player_entity: Entity,
+    mech_entity: Entity,
+) -> TransformationResult {
+    match world.get::<&Mech>(mech_entity) {
+        Ok(mech) if mech.is_occupied => {
+            return TransformationResult {
+                success: false,
+                message: "Mech is already occupied".to_string(),
+            };
+        }
+        Ok(mech) if mech.is_destroyed() => {
+            return TransformationResult {
+                success: false,
+                message: "Mech is wrecked".to_string(),
+            };
+        }
+        Ok(_) => {}
+        Err(_) => {
+            return TransformationResult {
+                success: false,
+                message: "Invalid mech entity".to_string(),
+            };
+        }
+    }
+
+    if world.get::<&MechTransition>(mech_entity).is_ok() {
+        return TransformationResult {
+            success: false,
+            message: "Mech is already transitioning".to_string(),
+        };
+    }
+
+    let (pilot_pos, pilot_health) = match world.query_one_mut::<(&Transform, &Player)>(player_entity) {
+        Ok((transform, player)) => (transform.position, player.health),
+        Err(_) => {
+            return TransformationResult {
+                success: false,
+                message: "Player has no transform".to_string(),
+            };
+        }
+    };
+    let mech_health = world.get::<&Mech>(mech_entity).map(|m| m.health).unwrap_or(0.0);
+
+    let transition = MechTransition::new(TransitionKind::Embark, player_entity, pilot_pos, pilot_health, mech_health);
+    let _ = world.insert_one(mech_entity, transition);
+    set_pilot_physics(world, player_entity, false);
+
+    TransformationResult {
+        success: true,
+        message: "Climbing into Titan mech".to_string(),
+    }
+}
+
+pub fn begin_disembark(
+    world: &mut World,
+    mech_entity: Entity,
+) -> TransformationResult {
+    let (pilot_entity, mech_pos, mech_health) = match world.query_one_mut::<(&Mech, &Transform)>(mech_entity) {
+        Ok((mech, transform)) => match mech.pilot_entity {
+            Some(pilot) if mech.is_occupied => (pilot, transform.position, mech.health),
+            _ => {
+                return TransformationResult {
+                    success: false,
+                    message: "Mech is not occupied".to_string(),
+                };
+            }
+        },
+        Err(_) => {
+            return TransformationResult {
+                success: false,
+                message: "Invalid mech entity".to_string(),
+            };
+        }
+    };
+
+    if world.get::<&MechTransition>(mech_entity).is_ok() {
+        return TransformationResult {
+            success: false,
+            message: "Mech is already transitioning".to_string(),
+        };
+    }
+
+    let pilot_health = world.get::<&Player>(pilot_entity).map(|p| p.health).unwrap_or(0.0);
+    let exit_pos = Vec2::new(mech_pos.x + 60.0, mech_pos.y);
+
+    let transition = MechTransition::new(TransitionKind::Disembark, pilot_entity, exit_pos, pilot_health, mech_health);
+    let _ = world.insert_one(mech_entity, transition);
+
+    TransformationResult {
+        success: true,
+        message: "Powering down Titan mech".to_string(),
+    }
+}
+
+/// Advance all running embark/disembark sequences
+pub fn mech_transition_system(world: &mut World, delta_time: f32) -> Vec<TransitionEvent> {
+    let mut events = Vec::new();
+    let mut particles_to_spawn = Vec::new();
+    let mut phase_changes = Vec::new();
+    let mut interrupted = Vec::new();
+
+    // Collect pilot health up front so damage can interrupt the sequence
+    let pilot_healths: Vec<(Entity, f32)> = world
+        .query::<&Player>()
+        .iter()
+        .map(|(entity, player)| (entity, player.health))
+        .collect();
+
+    for (mech_entity, (transition, mech, transform, body, sprite)) in world
+        .query_mut::<(&mut MechTransition, &Mech, &Transform, &mut RigidBody, &mut Sprite)>()
+    {
+        // No movement system drives the mech during the sequence, so hold it in place
+        // rather than let it coast away from the pilot
+        body.velocity.x = 0.0;
+
+        let pilot_health = pilot_healths
+            .iter()
+            .find(|(entity, _)| *entity == transition.pilot_entity)
+            .map(|(_, health)| *health)
+            .unwrap_or(transition.pilot_health);
+
+        if pilot_health < transition.pilot_health || mech.health < transition.mech_health {
+            interrupted.push((mech_entity, transition.clone()));
+            continue;
+        }
+
+        transition.phase_timer += delta_time;
+
+        // Hull color ramps with reactor power
+        sprite.color = mech.get_power_color(transition.power_level());
+
+        if transition.phase == TransitionPhase::Power {
+            transition.particle_timer -= delta_time;
+            if transition.particle_timer <= 0.0 {
+                transition.particle_timer = 0.05;
+                let spark_color = Mech::INACTIVE_COLOR.lerp(Color::new(180, 220, 255, 255), transition.power_level());
+                particles_to_spawn.extend(ParticleSystem::create_power_up_particles(
+                    transform.position,
+                    mech.size,
+                    spark_color,
+                    3,
+                ));
+            }
+        }
+
+        if transition.phase_timer >= transition.phase_duration() {
+            phase_changes.push((mech_entity, transition.clone(), transform.position, mech.size));
+        }
+    }
+
+    // Move pilots that are climbing between the ground and the hatch
+    let climbing: Vec<(Entity, MechTransition, Vec2, Vec2)> = world
+        .query::<(&MechTransition, &Mech, &Transform)>()
+        .iter()
+        .filter(|(_, (transition, _, _))| transition.phase == TransitionPhase::Climb)
+        .map(|(entity, (transition, mech, transform))| (entity, transition.clone(), transform.position, mech.size))
+        .collect();
+
+    for (_mech_entity, transition, mech_pos, mech_size) in climbing {
+        let hatch = hatch_position(mech_pos, mech_size);
+        let t = transition.phase_progress();
+        let (from, to) = match transition.kind {
+            TransitionKind::Embark => (transition.ground_position, hatch),
+            TransitionKind::Disembark => (hatch, transition.ground_position),
+        };
+        // Hop along an arc instead of sliding in a straight line
+        let arc = (t * std::f32::consts::PI).sin() * 40.0;
+        if let Ok((transform, body)) = world.query_one_mut::<(&mut Transform, &mut RigidBody)>(transition.pilot_entity) {
+            transform.position = from.lerp(to, t) - Vec2::new(0.0, arc);
+            body.velocity = Vec2::ZERO;
+        }
+    }
+
+    for (mech_entity, transition, mech_pos, mech_size) in phase_changes {
+        match (transition.kind, transition.phase) {
+            (TransitionKind::Embark, TransitionPhase::Climb) => {
+                // Pilot reached the hatch - seal them in and start the reactor
+                enter_mech(world, transition.pilot_entity, mech_entity);
+                if let Ok(mut running) = world.get::<&mut MechTransition>(mech_entity) {
+                    running.phase = TransitionPhase::Power;
+                    running.phase_timer = 0.0;
+                }
+            }
+            (TransitionKind::Embark, TransitionPhase::Power) => {
+                let _ = world.remove_one::<MechTransition>(mech_entity);
+                events.push(TransitionEvent::Embarked { mech: mech_entity });
+            }
+            (TransitionKind::Disembark, TransitionPhase::Power) => {
+                // Reactor is down - open the hatch and let the pilot climb out
+                exit_mech(world, mech_entity);
+                if let Ok(mut pilot_transform) = world.get::<&mut Transform>(transition.pilot_entity) {
+                    pilot_transform.position = hatch_position(mech_pos, mech_size);
+                }
+                if let Ok(mut running) = world.get::<&mut MechTransition>(mech_entity) {
+                    running.phase = TransitionPhase::Climb;
+                    running.phase_timer = 0.0;
+                }
+            }
+            (TransitionKind::Disembark, TransitionPhase::Climb) => {
+                let _ = world.remove_one::<MechTransition>(mech_entity);
+                set_pilot_physics(world, transition.pilot_entity, true);
+                events.push(TransitionEvent::Disembarked { mech: mech_entity });
+            }
+        }
+    }
+
+    for (mech_entity, transition) in interrupted {
+        let _ = world.remove_one::<MechTransition>(mech_entity);
+
+        let pilot_inside = match (transition.kind, transition.phase) {
+            // Hit while sealing up - eject back onto the ground
+            (TransitionKind::Embark, TransitionPhase::Power) => {
+                exit_mech(world, mech_entity);
+                false
+            }
+            // Hit while climbing in - stay on foot where we are
+            (TransitionKind::Embark, TransitionPhase::Climb) => false,
+            // Hit while powering down - abort and bring the reactor back online
+            (TransitionKind::Disembark, TransitionPhase::Power) => true,
+            // Hit while climbing out - drop straight to the ground
+            (TransitionKind::Disembark, TransitionPhase::Climb) => {
+                if let Ok(mut pilot_transform) = world.get::<&mut Transform>(transition.pilot_entity) {
+                    pilot_transform.position = transition.ground_position;
+                }
+                false
+            }
+        };
+
+        if let Ok((mech, sprite)) = world.query_one_mut::<(&Mech, &mut Sprite)>(mech_entity) {
+            sprite.color = mech.get_color();
+        }
+        if !pilot_inside {
+            set_pilot_physics(world, transition.pilot_entity, true);
+        }
+
+        info!("Mech transition interrupted by damage");
+        events.push(TransitionEvent::Interrupted { mech: mech_entity, pilot_inside });
+    }
+
+    for particle in particles_to_spawn {
+        spawn_particle(world, particle);
+    }
+
+    events
+}
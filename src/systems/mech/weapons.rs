@@ -1,9 +1,16 @@
 use glam::Vec2;
-use crate::engine::core::Color;
+use hecs::World;
+use sdl2::keyboard::Keycode;
+use sdl2::mouse::MouseButton;
+use crate::engine::core::{Color, Transform};
+use crate::engine::platform::InputState;
+use crate::engine::rendering::Camera;
 use crate::systems::weapons::{Weapon, WeaponType};
 use crate::systems::projectile::{Projectile, ProjectileOwner};
+use crate::systems::mech::{EnergyAllocation, Mech, MechLoadout, MechShield, PowerChannel};
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum MechWeaponType {
     PlasmaCanon,
     MissileLauncher,
@@ -11,6 +18,24 @@ pub enum MechWeaponType {
     FlameThrower,
 }
 
+impl MechWeaponType {
+    pub const ALL: [MechWeaponType; 4] = [
+        MechWeaponType::PlasmaCanon,
+        MechWeaponType::MissileLauncher,
+        MechWeaponType::Railgun,
+        MechWeaponType::FlameThrower,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MechWeaponType::PlasmaCanon => "PLASMA CANNON",
+            MechWeaponType::MissileLauncher => "MISSILE LAUNCHER",
+            MechWeaponType::Railgun => "RAILGUN",
+            MechWeaponType::FlameThrower => "FLAMETHROWER",
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MechWeapon {
     pub weapon_type: MechWeaponType,
@@ -24,6 +49,15 @@ pub struct MechWeapon {
 }
 
 impl MechWeapon {
+    pub fn from_type(weapon_type: MechWeaponType) -> Self {
+        match weapon_type {
+            MechWeaponType::PlasmaCanon => Self::plasma_canon(),
+            MechWeaponType::MissileLauncher => Self::missile_launcher(),
+            MechWeaponType::Railgun => Self::railgun(),
+            MechWeaponType::FlameThrower => Self::flamethrower(),
+        }
+    }
+
     pub fn plasma_canon() -> Self {
         Self {
             weapon_type: MechWeaponType::PlasmaCanon,
@@ -85,6 +119,13 @@ impl MechWeapon {
         projectile.size = self.projectile_size;
         projectile.color = self.projectile_color;
         projectile.max_lifetime = 3.0; // Mech projectiles last longer
+        projectile.gravity_scale = 0.0; // Heavy ordnance flies straight
+        projectile.glow_radius = match self.weapon_type {
+            MechWeaponType::PlasmaCanon => 130.0,
+            MechWeaponType::MissileLauncher => 100.0,
+            MechWeaponType::Railgun => 80.0,
+            MechWeaponType::FlameThrower => 50.0,
+        };
         if let Some(radius) = self.splash_radius {
             projectile.explosion_radius = radius;
         }
@@ -101,9 +142,13 @@ pub struct MechWeaponInventory {
 
 impl MechWeaponInventory {
     pub fn new() -> Self {
+        Self::from_loadout(&MechLoadout::default())
+    }
+
+    pub fn from_loadout(loadout: &MechLoadout) -> Self {
         Self {
-            primary: MechWeapon::plasma_canon(),
-            secondary: MechWeapon::missile_launcher(),
+            primary: MechWeapon::from_type(loadout.primary),
+            secondary: MechWeapon::from_type(loadout.secondary),
             primary_cooldown: 0.0,
             secondary_cooldown: 0.0,
         }
@@ -129,4 +174,67 @@ impl MechWeaponInventory {
     pub fn fire_secondary(&mut self) {
         self.secondary_cooldown = self.secondary.cooldown_time();
     }
-}
\ No newline at end of file
+}
+
+impl Default for MechWeaponInventory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Projectile fired by a mech, spawned by the caller
+pub struct MechShot {
+    pub position: Vec2,
+    pub direction: Vec2,
+    pub weapon: MechWeapon,
+}
+
+/// Piloted mech gunnery: left mouse fires the primary, Q the secondary, both at the cursor.
+/// Every shot costs energy and the shield has to be lowered to fire.
+pub fn mech_shooting_system(
+    world: &mut World,
+    input: &InputState,
+    camera: &Camera,
+    delta_time: f32,
+) -> Vec<MechShot> {
+    let mut shots = Vec::new();
+    let cursor = camera.screen_to_world(input.mouse_position());
+
+    for (_entity, (mech, weapons, transform, shield, allocation)) in world
+        .query_mut::<(&mut Mech, &mut MechWeaponInventory, &Transform, Option<&MechShield>, Option<&EnergyAllocation>)>()
+    {
+        if !mech.is_occupied {
+            continue;
+        }
+
+        weapons.update(delta_time * allocation.map_or(1.0, |a| a.multiplier(PowerChannel::Weapons)));
+
+        if shield.is_some_and(|s| s.is_raised) {
+            continue;
+        }
+
+        let direction = (cursor - transform.position).normalize_or_zero();
+        if direction == Vec2::ZERO {
+            continue;
+        }
+        let muzzle = transform.position + direction * mech.size.x / 2.0;
+
+        if input.is_mouse_button_down(MouseButton::Left)
+            && weapons.can_fire_primary()
+            && mech.use_energy(weapons.primary.energy_cost)
+        {
+            weapons.fire_primary();
+            shots.push(MechShot { position: muzzle, direction, weapon: weapons.primary.clone() });
+        }
+
+        if input.is_key_down(Keycode::Q)
+            && weapons.can_fire_secondary()
+            && mech.use_energy(weapons.secondary.energy_cost)
+        {
+            weapons.fire_secondary();
+            shots.push(MechShot { position: muzzle, direction, weapon: weapons.secondary.clone() });
+        }
+    }
+
+    shots
+}
@@ -5,7 +5,11 @@ pub mod weapons;
 pub mod particles;
 pub mod enemy_spawner;
 pub mod win_condition_system;
+pub mod trigger_system;
+pub mod respawn_system;
+pub mod animation_states;
 pub mod mech;
+pub mod lighting_system;
 
 pub use player::*;
 pub use enemy::*;
@@ -14,4 +18,8 @@ pub use weapons::*;
 pub use particles::*;
 pub use enemy_spawner::*;
 pub use win_condition_system::*;
+pub use trigger_system::*;
+pub use respawn_system::*;
+pub use animation_states::*;
 pub use mech::*;
+pub use lighting_system::*;
@@ -1,5 +1,5 @@
 use crate::engine::core::{Color, Transform};
-use crate::engine::rendering::Sprite;
+use crate::engine::rendering::{RenderLayer, Sprite};
 use glam::Vec2;
 use rand::Rng;
 
@@ -225,6 +225,80 @@ impl ParticleSystem {
         
         particles
     }
+
+    /// Chunks of rubble thrown up when a tile is destroyed
+    pub fn create_debris_particles(position: Vec2, color: Color, count: u32) -> Vec<Particle> {
+        let mut particles = Vec::new();
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..count {
+            let velocity = Vec2::new(
+                rng.gen_range(-150.0..150.0),
+                -rng.gen_range(100.0..300.0),
+            );
+
+            let mut particle = Particle::new(ParticleType::Impact, position, velocity);
+            particle.color = color;
+            particle.size = Vec2::splat(rng.gen_range(4.0..8.0));
+            particle.gravity_scale = 2.0;
+            particle.max_lifetime = rng.gen_range(0.5..0.9);
+            particles.push(particle);
+        }
+
+        // A puff of dust on top
+        for _ in 0..count / 2 {
+            let velocity = Vec2::new(rng.gen_range(-40.0..40.0), -rng.gen_range(10.0..40.0));
+            particles.push(Particle::new(ParticleType::Smoke, position, velocity));
+        }
+
+        particles
+    }
+
+    /// Ring of dust rolling outward along the ground from an impact
+    pub fn create_shockwave_particles(position: Vec2, radius: f32, count: u32) -> Vec<Particle> {
+        let mut particles = Vec::new();
+        let mut rng = rand::thread_rng();
+
+        for i in 0..count {
+            // Alternate sides so the wave spreads evenly both ways
+            let side = if i % 2 == 0 { 1.0 } else { -1.0 };
+            let speed = rng.gen_range(0.6..1.2) * radius * 2.0;
+            let velocity = Vec2::new(side * speed, -rng.gen_range(10.0..60.0));
+
+            let mut particle = Particle::new(ParticleType::Smoke, position, velocity);
+            particle.color = Color::new(150, 130, 110, 160);
+            particle.gravity_scale = 0.0;
+            particle.drag = 3.0;
+            particle.max_lifetime = rng.gen_range(0.6..1.0);
+            particles.push(particle);
+        }
+
+        particles
+    }
+
+    /// Energy sparks crawling up a mech's hull while it powers up or down
+    pub fn create_power_up_particles(position: Vec2, size: Vec2, color: Color, count: u32) -> Vec<Particle> {
+        let mut particles = Vec::new();
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..count {
+            let offset = Vec2::new(
+                rng.gen_range(-size.x / 2.0..size.x / 2.0),
+                rng.gen_range(-size.y / 2.0..size.y / 2.0),
+            );
+            let velocity = Vec2::new(
+                rng.gen_range(-30.0..30.0),
+                -rng.gen_range(60.0..160.0),
+            );
+
+            let mut particle = Particle::new(ParticleType::Spark, position + offset, velocity);
+            particle.color = color;
+            particle.gravity_scale = -0.1; // Drift upwards like static discharge
+            particles.push(particle);
+        }
+
+        particles
+    }
 }
 
 pub fn update_particles(world: &mut hecs::World, delta_time: f32) -> Vec<hecs::Entity> {
@@ -247,5 +321,6 @@ pub fn spawn_particle(world: &mut hecs::World, particle: Particle) {
         particle.clone(),
         Transform::new(particle.position),
         Sprite::new(particle.size, particle.color),
+        RenderLayer::Effects,
     ));
 }
\ No newline at end of file
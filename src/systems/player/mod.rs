@@ -1,7 +1,7 @@
-use crate::engine::core::{Rect, Transform};
-use crate::engine::physics::RigidBody;
-use crate::engine::rendering::Camera;
-use crate::game::Level;
+use crate::engine::core::{Color, Transform};
+use crate::engine::physics::{CharacterBody, RigidBody};
+use crate::engine::rendering::{Camera, PointLight};
+use crate::systems::lighting_system::spawn_light_flash;
 use crate::systems::weapons::{Weapon, WeaponInventory};
 use crate::systems::particles::{ParticleSystem, spawn_particle};
 use glam::Vec2;
@@ -27,6 +27,22 @@ impl Player {
             max_energy: 100.0,
         }
     }
+
+    pub fn is_dead(&self) -> bool {
+        self.health <= 0.0
+    }
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Ignores all damage until the timer runs out (given after a respawn)
+#[derive(Debug, Clone, Copy)]
+pub struct Invulnerable {
+    pub timer: f32,
 }
 
 pub struct PlayerController {
@@ -39,7 +55,11 @@ pub struct PlayerController {
     pub is_spinning: bool,
     pub spin_rotation: f32,
     pub spin_speed: f32,
+    /// 1.0 facing right, -1.0 facing left
+    pub facing: f32,
     pub shoot_timer: f32,
+    /// A shot went out this frame
+    pub just_fired: bool,
     pub weapon_inventory: WeaponInventory,
 }
 
@@ -55,7 +75,9 @@ impl PlayerController {
             is_spinning: false,
             spin_rotation: 0.0,
             spin_speed: 720.0, // 720 degrees per second (2 full rotations)
+            facing: 1.0,
             shoot_timer: 0.0,
+            just_fired: false,
             weapon_inventory: WeaponInventory::new()
         }
     }
@@ -84,15 +106,30 @@ impl Default for PlayerController {
 pub fn player_movement_system(
     world: &mut hecs::World,
     input: &crate::engine::platform::InputState,
-    level: &Level,
     delta_time: f32,
 ) {
-    for (_entity, (player, transform, body, controller)) in world.query_mut::<(
+    for (_entity, (player, transform, body, character, controller)) in world.query_mut::<(
         &mut Player,
         &mut Transform,
         &mut RigidBody,
+        &mut CharacterBody,
         &mut PlayerController,
     )>() {
+        // Landing resets the jump count and any unfinished spin
+        controller.is_grounded = character.is_grounded();
+        if controller.is_grounded {
+            controller.jump_count = 0;
+            if controller.is_spinning {
+                controller.is_spinning = false;
+                controller.spin_rotation = 0.0;
+                transform.rotation = 0.0;
+            }
+            // Regenerate energy while grounded
+            if player.energy < player.max_energy {
+                player.energy = (player.energy + 30.0 * delta_time).min(player.max_energy);
+            }
+        }
+
         let mut movement = Vec2::ZERO;
 
         if input.is_key_down(Keycode::A) || input.is_key_down(Keycode::Left) {
@@ -103,10 +140,16 @@ pub fn player_movement_system(
         }
 
         // Handle jumping (first jump from ground, second jump in air)
-        if input.is_key_pressed(Keycode::Space)
+        let jump_pressed = input.is_key_pressed(Keycode::Space)
             || input.is_key_pressed(Keycode::W)
-            || input.is_key_pressed(Keycode::Up)
-        {
+            || input.is_key_pressed(Keycode::Up);
+        let down_held = input.is_key_down(Keycode::S) || input.is_key_down(Keycode::Down);
+
+        if jump_pressed && down_held && controller.is_grounded && character.drop_through() {
+            // Down + Jump drops through the platform underfoot
+            controller.is_grounded = false;
+            controller.jump_count = 1;
+        } else if jump_pressed {
             if controller.is_grounded {
                 // First jump from ground
                 body.velocity.y = -controller.jump_force;
@@ -124,6 +167,9 @@ pub fn player_movement_system(
 
         movement = movement.normalize_or_zero();
         body.velocity.x = movement.x * controller.speed;
+        if movement.x != 0.0 {
+            controller.facing = movement.x.signum();
+        }
 
         // Handle spin flip animation during double jump
         if controller.is_spinning {
@@ -137,72 +183,6 @@ pub fn player_movement_system(
                 transform.rotation = 0.0;
             }
         }
-
-        body.apply_force(Vec2::new(0.0, 800.0));
-
-        body.update(delta_time);
-
-        let new_x = transform.position.x + body.velocity.x * delta_time;
-        let new_y = transform.position.y + body.velocity.y * delta_time;
-
-        let player_rect_x = Rect::new(
-            new_x - player.size.x / 2.0,
-            transform.position.y - player.size.y / 2.0,
-            player.size.x,
-            player.size.y,
-        );
-
-        if !level.check_collision(player_rect_x) {
-            transform.position.x = new_x;
-        } else {
-            body.velocity.x = 0.0;
-        }
-
-        let player_rect_y = Rect::new(
-            transform.position.x - player.size.x / 2.0,
-            new_y - player.size.y / 2.0,
-            player.size.x,
-            player.size.y,
-        );
-
-        if !level.check_collision(player_rect_y) {
-            transform.position.y = new_y;
-            controller.is_grounded = false;
-        } else {
-            if body.velocity.y > 0.0 {
-                controller.is_grounded = true;
-                controller.jump_count = 0;
-                // Reset spin when landing
-                if controller.is_spinning {
-                    controller.is_spinning = false;
-                    controller.spin_rotation = 0.0;
-                    transform.rotation = 0.0;
-                }
-            }
-            body.velocity.y = 0.0;
-        }
-
-        let ground_check = Rect::new(
-            transform.position.x - player.size.x / 2.0,
-            transform.position.y - player.size.y / 2.0 + 2.0,
-            player.size.x,
-            player.size.y,
-        );
-
-        if level.check_collision(ground_check) && body.velocity.y >= 0.0 {
-            controller.is_grounded = true;
-            controller.jump_count = 0;
-            // Reset spin when landing
-            if controller.is_spinning {
-                controller.is_spinning = false;
-                controller.spin_rotation = 0.0;
-                transform.rotation = 0.0;
-            }
-            // Regenerate energy while grounded
-            if player.energy < player.max_energy {
-                player.energy = (player.energy + 30.0 * delta_time).min(player.max_energy);
-            }
-        }
     }
 }
 
@@ -214,11 +194,13 @@ pub fn player_shooting_system(
 ) -> Vec<ProjectileSpawnData> {
     let mut projectiles_to_spawn = Vec::new();
     let mut particles_to_spawn = Vec::new();
+    let mut flashes = Vec::new();
     
     for (_entity, (transform, controller)) in world.query_mut::<(&Transform, &mut PlayerController)>()
         .with::<&Player>()
     {
         controller.update_timer(delta_time);
+        controller.just_fired = false;
         
         // Handle weapon switching
         if input.is_key_pressed(Keycode::Num1) {
@@ -293,8 +275,10 @@ pub fn player_shooting_system(
                     for particle in muzzle_particles {
                         particles_to_spawn.push(particle);
                     }
+                    flashes.push(muzzle_pos);
                     
                     controller.reset_shoot_timer();
+                    controller.just_fired = true;
                 }
             }
         }
@@ -304,6 +288,9 @@ pub fn player_shooting_system(
     for particle in particles_to_spawn {
         spawn_particle(world, particle);
     }
+    for position in flashes {
+        spawn_light_flash(world, position, PointLight::new(140.0, Color::new(255, 200, 120, 255)), 0.06);
+    }
     
     projectiles_to_spawn
 }
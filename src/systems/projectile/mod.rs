@@ -1,12 +1,15 @@
 use crate::engine::core::{Color, Rect, Transform};
-use crate::engine::physics::RigidBody;
-use crate::engine::rendering::Sprite;
+use crate::engine::physics::{CollisionFilter, RigidBody, SpatialHash};
+use crate::engine::rendering::PointLight;
 use crate::game::Level;
 use crate::systems::enemy::Enemy;
+use crate::systems::lighting_system::spawn_light_flash;
+use crate::systems::mech::{apply_mech_damage, Mech};
 use crate::systems::particles::{ParticleSystem, spawn_particle};
-use crate::systems::player::Player;
+use crate::systems::player::{Invulnerable, Player};
 use crate::systems::weapons::WeaponType;
 use glam::Vec2;
+use hecs::Entity;
 
 #[derive(Debug, Clone)]
 pub struct Projectile {
@@ -21,6 +24,8 @@ pub struct Projectile {
     pub pierce_count: u32,
     pub has_trail: bool,
     pub color: Color,
+    /// Radius of the light it casts, 0 for none
+    pub glow_radius: f32,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -29,6 +34,25 @@ pub enum ProjectileOwner {
     Player,
 }
 
+impl ProjectileOwner {
+    /// What a projectile fired by this side can hit
+    pub fn collision_filter(&self) -> CollisionFilter {
+        match self {
+            ProjectileOwner::Enemy => CollisionFilter::hostile_projectile(),
+            ProjectileOwner::Player => CollisionFilter::friendly_projectile(),
+        }
+    }
+}
+
+/// A projectile touching something it is allowed to hit
+struct ProjectileHit {
+    projectile: Entity,
+    target: Entity,
+    damage: f32,
+    velocity: Vec2,
+    position: Vec2,
+}
+
 impl Projectile {
     pub fn new(damage: f32, owner: ProjectileOwner) -> Self {
         Self {
@@ -43,6 +67,7 @@ impl Projectile {
             pierce_count: 0,
             has_trail: false,
             color: Color::new(255, 200, 0, 255),
+            glow_radius: 0.0,
         }
     }
     
@@ -63,6 +88,13 @@ impl Projectile {
                 WeaponType::PlasmaGun
             ),
             color: weapon.projectile_color,
+            // Energy and rocket shots light up their surroundings
+            glow_radius: match weapon.weapon_type {
+                WeaponType::PlasmaGun => 110.0,
+                WeaponType::RocketLauncher => 90.0,
+                WeaponType::LaserRifle => 60.0,
+                _ => 0.0,
+            },
         }
     }
 
@@ -71,38 +103,28 @@ impl Projectile {
     }
 }
 
+/// What a frame of projectile updates left behind
+pub struct ProjectileOutcome {
+    pub expired: Vec<hecs::Entity>,
+    pub particles: Vec<crate::systems::particles::Particle>,
+    /// Centre and blast radius of every explosion
+    pub explosions: Vec<(Vec2, f32)>,
+}
+
 pub fn projectile_system(
     world: &mut hecs::World,
     level: &Level,
+    broadphase: &SpatialHash,
     delta_time: f32,
-) -> (Vec<hecs::Entity>, Vec<crate::systems::particles::Particle>) {
+) -> ProjectileOutcome {
     let mut expired_projectiles = Vec::new();
-    let mut player_hits = Vec::new();
-    let mut enemy_hits = Vec::new();
+    let mut hits = Vec::new();
     let mut particles_to_spawn = Vec::new();
+    let mut explosions = Vec::new();
     
-    // Collect player info
-    let mut player_info = None;
-    for (_player_entity, (player, player_transform)) in world
-        .query::<(&Player, &Transform)>()
-        .iter()
-    {
-        player_info = Some((player_transform.position, player.size));
-        break;
-    }
-    
-    // Collect enemy info
-    let mut enemy_info = Vec::new();
-    for (enemy_entity, (enemy, enemy_transform)) in world
-        .query::<(&Enemy, &Transform)>()
-        .iter()
-    {
-        enemy_info.push((enemy_entity, enemy_transform.position, enemy.size));
-    }
-
     // Process projectiles
-    for (proj_entity, (projectile, proj_transform, proj_body)) in world
-        .query_mut::<(&mut Projectile, &mut Transform, &mut RigidBody)>()
+    for (proj_entity, (projectile, proj_transform, proj_body, filter)) in world
+        .query_mut::<(&mut Projectile, &mut Transform, &mut RigidBody, Option<&CollisionFilter>)>()
     {
         projectile.lifetime += delta_time;
         
@@ -153,6 +175,7 @@ pub fn projectile_system(
                     projectile.explosion_radius / 50.0
                 );
                 particles_to_spawn.extend(explosion_particles);
+                explosions.push((proj_transform.position, projectile.explosion_radius));
                 
                 // TODO: Apply explosion damage in radius
             }
@@ -163,76 +186,54 @@ pub fn projectile_system(
         
         proj_transform.position = new_pos;
 
-        // Check collisions based on owner
-        if projectile.owner_type == ProjectileOwner::Enemy {
-            // Check collision with player
-            if let Some((player_pos, player_size)) = player_info {
-                let player_rect = Rect::new(
-                    player_pos.x - player_size.x / 2.0,
-                    player_pos.y - player_size.y / 2.0,
-                    player_size.x,
-                    player_size.y,
-                );
+        // Hit whatever the projectile's collision filter lets it touch
+        let filter = filter.copied().unwrap_or_else(|| projectile.owner_type.collision_filter());
+        for target in broadphase.query_filtered(proj_rect, &filter) {
+            hits.push(ProjectileHit {
+                projectile: proj_entity,
+                target,
+                damage: projectile.damage,
+                velocity: proj_body.velocity,
+                position: proj_transform.position,
+            });
 
-                if player_rect.intersects(&proj_rect) {
-                    player_hits.push((proj_entity, projectile.damage));
-                    
-                    // Create impact effects
-                    let impact_particles = ParticleSystem::create_impact_particles(
-                        proj_transform.position,
-                        proj_body.velocity.normalize_or_zero(),
-                        8
-                    );
-                    particles_to_spawn.extend(impact_particles);
-                }
-            }
-        } else if projectile.owner_type == ProjectileOwner::Player {
-            // Check collision with enemies
-            for (enemy_entity, enemy_pos, enemy_size) in &enemy_info {
-                let enemy_rect = Rect::new(
-                    enemy_pos.x - enemy_size.x / 2.0,
-                    enemy_pos.y - enemy_size.y / 2.0,
-                    enemy_size.x,
-                    enemy_size.y,
-                );
-
-                if enemy_rect.intersects(&proj_rect) {
-                    enemy_hits.push((proj_entity, *enemy_entity, projectile.damage));
-                    
-                    // Create impact effects
-                    let impact_particles = ParticleSystem::create_impact_particles(
-                        proj_transform.position,
-                        proj_body.velocity.normalize_or_zero(),
-                        8
-                    );
-                    particles_to_spawn.extend(impact_particles);
-                    
-                    // Handle piercing
-                    if projectile.pierce_count > 0 {
-                        projectile.pierce_count -= 1;
-                    } else {
-                        break; // Projectile can only hit one enemy if no pierce
-                    }
-                }
+            // Handle piercing
+            if projectile.pierce_count > 0 {
+                projectile.pierce_count -= 1;
+            } else {
+                break; // Projectile can only hit one target if no pierce
             }
         }
     }
 
-    // Apply damage to player
-    for (proj_entity, damage) in player_hits {
-        for (_player_entity, player) in world.query_mut::<&mut Player>() {
-            player.health = (player.health - damage).max(0.0);
-        }
-        expired_projectiles.push(proj_entity);
+    for &(position, radius) in &explosions {
+        let light = PointLight::new(radius * 3.0, Color::new(255, 170, 80, 255)).with_intensity(1.5);
+        spawn_light_flash(world, position, light, 0.3);
     }
-    
-    // Apply damage to enemies
-    for (proj_entity, enemy_entity, damage) in enemy_hits {
-        if let Ok(mut enemy) = world.get::<&mut Enemy>(enemy_entity) {
-            enemy.health = (enemy.health - damage).max(0.0);
+
+    for hit in hits {
+        let direction = hit.velocity.normalize_or_zero();
+        if let Ok((player, invulnerable)) = world.query_one_mut::<(&mut Player, Option<&Invulnerable>)>(hit.target) {
+            if invulnerable.is_none() {
+                player.health = (player.health - hit.damage).max(0.0);
+            }
+            particles_to_spawn.extend(ParticleSystem::create_impact_particles(hit.position, direction, 8));
+        } else if world.satisfies::<&Mech>(hit.target).unwrap_or(false) {
+            // Shields soak up hits from the front
+            if apply_mech_damage(world, hit.target, hit.damage, hit.velocity) {
+                for mut particle in ParticleSystem::create_impact_particles(hit.position, direction, 10) {
+                    particle.color = Color::new(80, 200, 255, 255); // Shield ripple
+                    particles_to_spawn.push(particle);
+                }
+            } else {
+                particles_to_spawn.extend(ParticleSystem::create_impact_particles(hit.position, direction, 8));
+            }
+        } else if let Ok(enemy) = world.query_one_mut::<&mut Enemy>(hit.target) {
+            enemy.health = (enemy.health - hit.damage).max(0.0);
             enemy.show_health_bar(); // Show health bar when hit
+            particles_to_spawn.extend(ParticleSystem::create_impact_particles(hit.position, direction, 8));
         }
-        expired_projectiles.push(proj_entity);
+        expired_projectiles.push(hit.projectile);
     }
 
     // Remove dead enemies
@@ -246,7 +247,11 @@ pub fn projectile_system(
         let _ = world.despawn(entity);
     }
 
-    (expired_projectiles, particles_to_spawn)
+    ProjectileOutcome {
+        expired: expired_projectiles,
+        particles: particles_to_spawn,
+        explosions,
+    }
 }
 
 // This function is no longer needed as projectile positions are updated in projectile_system
\ No newline at end of file
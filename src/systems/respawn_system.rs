@@ -0,0 +1,201 @@
+use crate::engine::core::{Color, Transform};
+use crate::engine::physics::{Collider, CollisionFilter, RigidBody, SpatialHash};
+use crate::engine::rendering::{RenderLayer, Sprite};
+use crate::game::LevelManager;
+use crate::systems::enemy::Enemy;
+use crate::systems::mech::{eject_pilot, Mech};
+use crate::systems::player::{Invulnerable, Player};
+use crate::systems::projectile::{Projectile, ProjectileOwner};
+use glam::Vec2;
+use hecs::{Entity, World};
+
+/// Seconds the player can't be hurt after respawning
+pub const RESPAWN_INVULNERABILITY: f32 = 2.0;
+/// Enemies and enemy fire this close to the respawn point are cleared away
+pub const ENEMY_RESET_RADIUS: f32 = 900.0;
+pub const STARTING_LIVES: u32 = 3;
+pub const STARTING_CONTINUES: u32 = 2;
+
+/// Where a recovered mech is parked relative to the respawn point
+const MECH_PARK_OFFSET: Vec2 = Vec2::new(200.0, 0.0);
+const CHECKPOINT_SIZE: Vec2 = Vec2::new(16.0, 64.0);
+const CHECKPOINT_IDLE_COLOR: Color = Color { r: 120, g: 120, b: 140, a: 255 };
+const CHECKPOINT_ACTIVE_COLOR: Color = Color { r: 60, g: 220, b: 255, a: 255 };
+
+/// A checkpoint flag in the world
+#[derive(Debug, Clone)]
+pub struct CheckpointMarker {
+    /// Index into the level's checkpoints
+    pub index: usize,
+}
+
+/// What losing a life leads to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeathOutcome {
+    /// Back to the last checkpoint
+    Respawn,
+    /// Out of lives - a continue restarts the level with a full set
+    Continue,
+    GameOver,
+}
+
+/// Lives left before a continue is spent, and continues left before game over
+#[derive(Debug, Clone)]
+pub struct Lives {
+    pub lives: u32,
+    pub continues: u32,
+}
+
+impl Lives {
+    pub fn new() -> Self {
+        Self {
+            lives: STARTING_LIVES,
+            continues: STARTING_CONTINUES,
+        }
+    }
+
+    pub fn lose_life(&mut self) -> DeathOutcome {
+        if self.lives > 1 {
+            self.lives -= 1;
+            DeathOutcome::Respawn
+        } else if self.continues > 0 {
+            self.continues -= 1;
+            self.lives = STARTING_LIVES;
+            DeathOutcome::Continue
+        } else {
+            self.lives = 0;
+            DeathOutcome::GameOver
+        }
+    }
+}
+
+impl Default for Lives {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Put a flag at each of the current level's checkpoints
+pub fn spawn_checkpoints(world: &mut World, level_manager: &LevelManager) {
+    let level = level_manager.get_current_level();
+    for (index, &position) in level.checkpoints.iter().enumerate() {
+        world.spawn((
+            CheckpointMarker { index },
+            Transform::new(position),
+            Collider::Box { size: CHECKPOINT_SIZE },
+            CollisionFilter::trigger(),
+            Sprite::new(CHECKPOINT_SIZE, CHECKPOINT_IDLE_COLOR),
+            RenderLayer::Gameplay,
+        ));
+    }
+}
+
+/// Activates checkpoints the player or a piloted mech touches and lights up the active flag.
+/// Returns true when a new checkpoint was reached this frame.
+pub fn checkpoint_system(world: &mut World, level_manager: &mut LevelManager, broadphase: &SpatialHash) -> bool {
+    let mut reached = false;
+    let touched: Vec<usize> = world
+        .query::<&CheckpointMarker>()
+        .iter()
+        .filter(|(entity, _)| {
+            broadphase.get(*entity).is_some_and(|entry| {
+                broadphase.query_filtered(entry.bounds, &entry.filter).into_iter().any(|other| {
+                    world.satisfies::<&Player>(other).unwrap_or(false)
+                        || world.get::<&Mech>(other).is_ok_and(|mech| mech.is_occupied)
+                })
+            })
+        })
+        .map(|(_, marker)| marker.index)
+        .collect();
+    for index in touched {
+        reached |= level_manager.activate_checkpoint(index);
+    }
+
+    let active = level_manager.get_active_checkpoint();
+    for (_entity, (marker, sprite)) in world.query_mut::<(&CheckpointMarker, &mut Sprite)>() {
+        sprite.color = if active == Some(marker.index) { CHECKPOINT_ACTIVE_COLOR } else { CHECKPOINT_IDLE_COLOR };
+    }
+    reached
+}
+
+/// The player is out of health on foot, or the mech they're piloting has been destroyed
+/// (a pilot inside a mech can't be hurt)
+pub fn player_died(world: &World, player_entity: Entity) -> bool {
+    world.get::<&Player>(player_entity).is_ok_and(|player| player.is_dead())
+        || piloted_mech(world, player_entity).is_some_and(|mech| world.get::<&Mech>(mech).is_ok_and(|mech| mech.is_destroyed()))
+}
+
+/// The mech the player is sealed inside, if any
+fn piloted_mech(world: &World, player_entity: Entity) -> Option<Entity> {
+    world
+        .query::<&Mech>()
+        .iter()
+        .find(|(_, mech)| mech.is_occupied && mech.pilot_entity == Some(player_entity))
+        .map(|(entity, _)| entity)
+}
+
+/// After a death in the cockpit: eject the pilot and patch the mech up, parked beside `position`.
+/// Returns the mech when the player was piloting one.
+pub fn recover_piloted_mech(world: &mut World, player_entity: Entity, position: Vec2) -> Option<Entity> {
+    let mech_entity = piloted_mech(world, player_entity)?;
+    eject_pilot(world, mech_entity);
+    if let Ok((mech, transform, body)) = world.query_one_mut::<(&mut Mech, &mut Transform, &mut RigidBody)>(mech_entity) {
+        mech.health = mech.max_health;
+        mech.energy = mech.max_energy;
+        transform.position = position + MECH_PARK_OFFSET;
+        body.velocity = Vec2::ZERO;
+    }
+    Some(mech_entity)
+}
+
+/// Bring the player back at a position with full health and a moment of invulnerability
+pub fn respawn_player(world: &mut World, player_entity: Entity, position: Vec2) {
+    if let Ok((player, transform, body)) = world.query_one_mut::<(&mut Player, &mut Transform, &mut RigidBody)>(player_entity) {
+        transform.position = position;
+        body.velocity = Vec2::ZERO;
+        player.health = player.max_health;
+        player.energy = player.max_energy;
+    }
+    let _ = world.insert_one(player_entity, Invulnerable { timer: RESPAWN_INVULNERABILITY });
+}
+
+/// Clear enemies and their shots around a respawn point so the player isn't dropped into a fight
+pub fn reset_enemies_near(world: &mut World, position: Vec2, radius: f32) {
+    let mut cleared: Vec<Entity> = world
+        .query::<(&Enemy, &Transform)>()
+        .iter()
+        .filter(|(_, (_, transform))| transform.position.distance(position) <= radius)
+        .map(|(entity, _)| entity)
+        .collect();
+    cleared.extend(
+        world
+            .query::<(&Projectile, &Transform)>()
+            .iter()
+            .filter(|(_, (projectile, transform))| {
+                projectile.owner_type == ProjectileOwner::Enemy && transform.position.distance(position) <= radius
+            })
+            .map(|(entity, _)| entity),
+    );
+    for entity in cleared {
+        let _ = world.despawn(entity);
+    }
+}
+
+/// Counts down invulnerability, blinking the sprite while it lasts
+pub fn invulnerability_system(world: &mut World, delta_time: f32) {
+    let mut expired = Vec::new();
+    for (entity, (invulnerable, sprite)) in world.query_mut::<(&mut Invulnerable, Option<&mut Sprite>)>() {
+        invulnerable.timer -= delta_time;
+        let done = invulnerable.timer <= 0.0;
+        if let Some(sprite) = sprite {
+            let visible = done || (invulnerable.timer * 10.0) as i32 % 2 == 0;
+            sprite.color.a = if visible { 255 } else { 80 };
+        }
+        if done {
+            expired.push(entity);
+        }
+    }
+    for entity in expired {
+        let _ = world.remove_one::<Invulnerable>(entity);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::engine::core::{Color, Transform};
+use crate::engine::physics::{CollisionFilter, SpatialHash};
+use crate::engine::rendering::{CameraController, Renderer, Sprite};
+use crate::game::{Level, LevelManager, TriggerAction, TriggerContext, TriggerSubject, UIManager};
+use crate::systems::enemy::Enemy;
+use crate::systems::enemy_spawner::EnemySpawner;
+use crate::systems::mech::Mech;
+use crate::systems::player::Player;
+use glam::Vec2;
+use hecs::World;
+use log::info;
+
+/// Works out which of the current level's triggers fire this frame and returns their actions in order
+pub fn trigger_system(
+    world: &World,
+    level_manager: &mut LevelManager,
+    broadphase: &SpatialHash,
+    delta_time: f32,
+) -> Vec<TriggerAction> {
+    let enemy_count = world.query::<&Enemy>().iter().count();
+    let items_collected = level_manager.get_items_collected();
+    let level_time = level_manager.get_level_time();
+    let level = level_manager.get_current_level_mut();
+
+    let fired: Vec<String> = level
+        .triggers
+        .iter()
+        .filter(|trigger| trigger.fire_count > 0)
+        .map(|trigger| trigger.name.clone())
+        .collect();
+    let context = TriggerContext {
+        enemy_count,
+        items_collected,
+        level_time,
+        fired: &fired,
+    };
+
+    let mut actions = Vec::new();
+    for trigger in &mut level.triggers {
+        if trigger.is_spent() {
+            continue;
+        }
+
+        // Triggers only see the player and mechs, so only those can be inside
+        let inside = broadphase
+            .query_filtered(trigger.rect, &CollisionFilter::trigger())
+            .into_iter()
+            .any(|entity| {
+                let is_player = world.satisfies::<&Player>(entity).unwrap_or(false);
+                let is_mech = world.satisfies::<&Mech>(entity).unwrap_or(false);
+                match trigger.subject {
+                    TriggerSubject::Player => is_player,
+                    TriggerSubject::Mech => is_mech,
+                    TriggerSubject::Either => is_player || is_mech,
+                }
+            });
+
+        if trigger.update(inside, delta_time) && trigger.conditions.iter().all(|c| c.is_met(&context)) {
+            info!("Trigger fired: {}", trigger.name);
+            actions.extend(trigger.fire());
+        }
+    }
+    actions
+}
+
+/// Carry out one trigger action
+pub fn apply_trigger_action(
+    world: &mut World,
+    level_manager: &mut LevelManager,
+    enemy_spawner: &EnemySpawner,
+    ui_manager: &mut UIManager,
+    camera: &mut CameraController,
+    action: TriggerAction,
+) {
+    match action {
+        TriggerAction::SpawnWave { position, count, spread } => {
+            enemy_spawner.spawn_wave(world, position, count, spread);
+        }
+        TriggerAction::ShowMessage { text, duration } => {
+            ui_manager.show_message(&text, duration);
+        }
+        TriggerAction::SetTiles { x, y, width, height, tile_type } => {
+            let level = level_manager.get_current_level_mut();
+            for tile_y in y..y + height {
+                for tile_x in x..x + width {
+                    level.set_tile(tile_x, tile_y, tile_type);
+                }
+            }
+        }
+        TriggerAction::StartCutscene { caption, duration } => {
+            ui_manager.start_cutscene(&caption, duration);
+        }
+        TriggerAction::FrameCamera { points, duration } => {
+            camera.frame(points, duration);
+        }
+        TriggerAction::SetCheckpoint(position) => {
+            level_manager.set_checkpoint(position);
+        }
+        TriggerAction::CompleteObjective => {
+            level_manager.complete_objective();
+        }
+    }
+}
+
+/// Debug view of every trigger: armed ones in cyan, occupied in yellow, spent in grey
+pub fn draw_trigger_volumes(renderer: &mut Renderer, level: &Level) {
+    for trigger in &level.triggers {
+        let color = if trigger.is_spent() {
+            Color::new(140, 140, 140, 255)
+        } else if trigger.occupied {
+            Color::new(255, 220, 60, 255)
+        } else {
+            Color::new(60, 220, 255, 255)
+        };
+        let rect = trigger.rect;
+        let center = Vec2::new(rect.x + rect.width / 2.0, rect.y + rect.height / 2.0);
+
+        let fill = Sprite::new(Vec2::new(rect.width, rect.height), Color::new(color.r, color.g, color.b, 40));
+        renderer.draw_sprite(&fill, &Transform::new(center));
+
+        let edge = 2.0 / renderer.camera.zoom;
+        let horizontal = Sprite::new(Vec2::new(rect.width, edge), color);
+        let vertical = Sprite::new(Vec2::new(edge, rect.height), color);
+        renderer.draw_sprite(&horizontal, &Transform::new(Vec2::new(center.x, rect.y)));
+        renderer.draw_sprite(&horizontal, &Transform::new(Vec2::new(center.x, rect.y + rect.height)));
+        renderer.draw_sprite(&vertical, &Transform::new(Vec2::new(rect.x, center.y)));
+        renderer.draw_sprite(&vertical, &Transform::new(Vec2::new(rect.x + rect.width, center.y)));
+
+        let label_pos = renderer.camera.world_to_screen(Vec2::new(rect.x, rect.y)) + Vec2::new(4.0, 4.0);
+        renderer.draw_ui_text(label_pos, &trigger.name.to_uppercase().replace('_', " "), color, 12);
+    }
+}
@@ -199,4 +199,10 @@ impl WeaponInventory {
             self.current_weapon_index -= 1;
         }
     }
-}
\ No newline at end of file
+}
+
+impl Default for WeaponInventory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,10 +1,12 @@
 use crate::game::{LevelManager, WinProgress};
 use crate::systems::enemy::Enemy;
+use crate::systems::mech::Mech;
 use crate::systems::player::Player;
-use crate::engine::core::{Transform, Color, Rect};
-use crate::engine::rendering::Sprite;
+use crate::engine::core::{Transform, Color};
+use crate::engine::physics::{Collider, CollisionFilter, SpatialHash};
+use crate::engine::rendering::{RenderLayer, Sprite};
 use glam::Vec2;
-use hecs::World;
+use hecs::{Entity, World};
 
 pub fn check_win_conditions(
     world: &mut World,
@@ -24,58 +26,69 @@ pub fn check_win_conditions(
     level_manager.check_win_condition(player_pos, enemy_count)
 }
 
+/// Picks up collectibles touched by the player or a piloted mech.
+/// Returns the collected markers so they can be despawned.
 pub fn check_collectibles(
-    world: &mut World,
+    world: &World,
     level_manager: &mut LevelManager,
-) {
-    let mut player_rect = None;
-    
-    // Get player rect
-    for (_entity, (player, transform)) in world.query::<(&Player, &Transform)>().iter() {
-        player_rect = Some(Rect::new(
-            transform.position.x - player.size.x / 2.0,
-            transform.position.y - player.size.y / 2.0,
-            player.size.x,
-            player.size.y,
-        ));
-        break;
-    }
-    
-    if let Some(p_rect) = player_rect {
-        let collectibles = level_manager.get_collectibles_status();
-        
-        for (index, (pos, collected)) in collectibles.iter().enumerate() {
-            if !collected {
-                // Check if player is touching this collectible
-                let collectible_rect = Rect::new(
-                    pos.x - 16.0,
-                    pos.y - 16.0,
-                    32.0,
-                    32.0,
-                );
-                
-                if p_rect.intersects(&collectible_rect) {
-                    level_manager.collect_item(index);
+    broadphase: &SpatialHash,
+) -> Vec<Entity> {
+    // A pilot inside a mech has no collider, so the mech does the collecting
+    let mut collectors: Vec<Entity> = world
+        .query::<&Player>()
+        .with::<&Collider>()
+        .iter()
+        .map(|(entity, _)| entity)
+        .collect();
+    collectors.extend(
+        world
+            .query::<&Mech>()
+            .iter()
+            .filter(|(_, mech)| mech.is_occupied)
+            .map(|(entity, _)| entity),
+    );
+
+    let mut collected = Vec::new();
+    for collector in collectors {
+        let Some(entry) = broadphase.get(collector) else {
+            continue;
+        };
+        for entity in broadphase.query_filtered(entry.bounds, &entry.filter) {
+            if let Ok(marker) = world.get::<&CollectibleMarker>(entity) {
+                if level_manager.collect_item(marker.index) {
+                    collected.push(entity);
                 }
             }
         }
     }
+    collected
 }
 
 pub fn spawn_collectibles(world: &mut World, level_manager: &LevelManager) {
     let collectibles = level_manager.get_collectibles_status();
     
-    for (pos, collected) in collectibles {
+    for (index, (pos, collected)) in collectibles.into_iter().enumerate() {
         if !collected {
             world.spawn((
-                CollectibleMarker,
+                CollectibleMarker { index },
                 Transform::new(pos),
+                Collider::Box { size: Vec2::new(32.0, 32.0) },
+                CollisionFilter::pickup(),
                 Sprite::new(Vec2::new(32.0, 32.0), Color::new(100, 200, 255, 255)), // Blue collectible
             ));
         }
     }
 }
 
+/// Replace the collectible markers with a fresh set, e.g. after progress was rolled back
+pub fn respawn_collectibles(world: &mut World, level_manager: &LevelManager) {
+    let markers: Vec<Entity> = world.query::<&CollectibleMarker>().iter().map(|(entity, _)| entity).collect();
+    for entity in markers {
+        let _ = world.despawn(entity);
+    }
+    spawn_collectibles(world, level_manager);
+}
+
 pub fn spawn_goal_marker(world: &mut World, level_manager: &LevelManager) {
     let level = level_manager.get_current_level();
     
@@ -84,13 +97,17 @@ pub fn spawn_goal_marker(world: &mut World, level_manager: &LevelManager) {
             GoalMarker,
             Transform::new(goal_pos),
             Sprite::new(Vec2::new(64.0, 64.0), Color::new(50, 255, 50, 200)), // Green goal
+            RenderLayer::Gameplay, // Behind whoever reaches it
         ));
     }
 }
 
 // Marker components
 #[derive(Debug, Clone)]
-pub struct CollectibleMarker;
+pub struct CollectibleMarker {
+    /// Index into the level's collectibles
+    pub index: usize,
+}
 
 #[derive(Debug, Clone)]
 pub struct GoalMarker;
\ No newline at end of file